@@ -0,0 +1,285 @@
+use crate::{fabric::version::FabricVersion, forge::version::ForgeVersion, jar::Loader};
+
+/// What a version string or file name says about the mod version, the Minecraft version
+/// and the loader, for when the mod's metadata is missing.
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Guess {
+    pub mod_version: String,
+    pub minecraft: Option<String>,
+    pub loader: Option<Loader>,
+    pub confidence: Confidence,
+}
+
+/// How sure a [`Guess`] is of the Minecraft version, ordered from least to most sure.
+#[derive(PartialEq, Eq, Debug, Clone, Copy, PartialOrd, Ord)]
+pub enum Confidence {
+    /// No Minecraft version was found.
+    Low,
+    /// A version that looks like Minecraft's next to the mod version, e.g. `1.20.1-0.5.3`.
+    Medium,
+    /// Marked by a `mc` prefix or the build metadata, e.g. `1.4.2+mc1.20.1`.
+    High,
+}
+
+pub fn from_fabric(version: &FabricVersion) -> Option<Guess> {
+    from_version(&version.to_string())
+}
+
+pub fn from_forge(version: &ForgeVersion) -> Option<Guess> {
+    from_version(&version.to_string())
+}
+
+/// Guess from a version string, e.g. `1.4.2+mc1.20.1` or `fabric-1.19.2-3.1.0`.
+pub fn from_version(version: &str) -> Option<Guess> {
+    guess(version.trim())
+}
+
+/// Guess from the name of a jar, skipping the mod name before the version,
+/// e.g. `sodium-fabric-0.5.3+mc1.20.1.jar`.
+pub fn from_file_name(name: &str) -> Option<Guess> {
+    guess(name.strip_suffix(".jar").unwrap_or(name))
+}
+
+fn guess(s: &str) -> Option<Guess> {
+    let (core, build) = match s.split_once('+') {
+        Some((core, build)) => (core, Some(build)),
+        None => (s, None),
+    };
+
+    let mut loader = None;
+    let mut minecraft = None;
+    let mut confidence = Confidence::Low;
+
+    let mut version = vec![];
+    for segment in core.split(['-', '_']) {
+        if let Some(tag) = loader_tag(segment) {
+            loader.get_or_insert(tag);
+        } else if minecraft.is_none()
+            && let Some(mc) = strip_mc(segment)
+            && is_minecraft_version(mc)
+        {
+            minecraft = Some(mc.to_string());
+            confidence = Confidence::High;
+        } else if !version.is_empty() || starts_version(segment) {
+            version.push(segment);
+        }
+    }
+
+    let mut rest = vec![];
+    for segment in build.into_iter().flat_map(|build| build.split(['-', '_'])) {
+        let mc = strip_mc(segment).unwrap_or(segment);
+        if let Some(tag) = loader_tag(segment) {
+            loader.get_or_insert(tag);
+        } else if minecraft.is_none() && is_minecraft_version(mc) {
+            minecraft = Some(mc.to_string());
+            confidence = Confidence::High;
+        } else {
+            rest.push(segment);
+        }
+    }
+
+    // Without a marker, look for the Minecraft version on either side of the mod version.
+    // When both sides look like one, e.g. `1.0.0-1.20.1`, the newer one is taken.
+    if minecraft.is_none()
+        && let [first, second, ..] = version[..]
+    {
+        let last = version[version.len() - 1];
+        let first_is_mc = is_minecraft_version(first) && starts_version(second);
+        let last_is_mc = is_minecraft_version(last) && starts_version(first);
+        if last_is_mc
+            && (!first_is_mc || ForgeVersion::parse(last).ok() > ForgeVersion::parse(first).ok())
+        {
+            minecraft = version.pop().map(str::to_string);
+            confidence = Confidence::Medium;
+        } else if first_is_mc {
+            minecraft = Some(version.remove(0).to_string());
+            confidence = Confidence::Medium;
+        }
+    }
+
+    if version.is_empty() {
+        return None;
+    }
+    let mut mod_version = version.join("-");
+    if !rest.is_empty() {
+        mod_version.push('+');
+        mod_version.push_str(&rest.join("-"));
+    }
+
+    Some(Guess {
+        mod_version,
+        minecraft,
+        loader,
+        confidence,
+    })
+}
+
+fn loader_tag(segment: &str) -> Option<Loader> {
+    [
+        ("fabric", Loader::Fabric),
+        ("quilt", Loader::Quilt),
+        ("forge", Loader::Forge),
+        ("neoforge", Loader::NeoForge),
+    ]
+    .into_iter()
+    .find(|(tag, _)| segment.eq_ignore_ascii_case(tag))
+    .map(|(_, loader)| loader)
+}
+
+fn strip_mc(segment: &str) -> Option<&str> {
+    segment
+        .strip_prefix("mc")
+        .or_else(|| segment.strip_prefix("MC"))
+}
+
+/// A digit or `v` followed by a digit.
+fn starts_version(segment: &str) -> bool {
+    let segment = segment.strip_prefix('v').unwrap_or(segment);
+    segment.starts_with(|ch: char| ch.is_ascii_digit())
+}
+
+/// A release such as `1.20` or `1.20.1`, or a snapshot such as `23w45a`.
+fn is_minecraft_version(s: &str) -> bool {
+    let is_number = |part: &str| !part.is_empty() && part.bytes().all(|b| b.is_ascii_digit());
+
+    let parts: Vec<&str> = s.split('.').collect();
+    let is_release =
+        matches!(parts[..], ["1", _] | ["1", _, _]) && parts.iter().all(|p| is_number(p));
+
+    let is_snapshot = match s.split_once('w') {
+        Some((year, week)) => {
+            year.len() == 2
+                && is_number(year)
+                && week.len() == 3
+                && is_number(&week[..2])
+                && week[2..].bytes().all(|b| b.is_ascii_lowercase())
+        }
+        None => false,
+    };
+
+    is_release || is_snapshot
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn guess(s: &str) -> (String, Option<String>, Option<Loader>, Confidence) {
+        let guess = from_file_name(s).unwrap();
+        (
+            guess.mod_version,
+            guess.minecraft,
+            guess.loader,
+            guess.confidence,
+        )
+    }
+
+    #[test]
+    fn guess_from_version() {
+        let mc = |s: &str| Some(s.to_string());
+
+        assert_eq!(
+            guess("1.4.2+mc1.20.1"),
+            ("1.4.2".into(), mc("1.20.1"), None, Confidence::High)
+        );
+        assert_eq!(
+            guess("1.20.1-0.5.3"),
+            ("0.5.3".into(), mc("1.20.1"), None, Confidence::Medium)
+        );
+        assert_eq!(
+            guess("fabric-1.19.2-3.1.0"),
+            (
+                "3.1.0".into(),
+                mc("1.19.2"),
+                Some(Loader::Fabric),
+                Confidence::Medium
+            )
+        );
+        assert_eq!(
+            guess("0.5.3-beta.1-1.20.1"),
+            (
+                "0.5.3-beta.1".into(),
+                mc("1.20.1"),
+                None,
+                Confidence::Medium
+            )
+        );
+        assert_eq!(
+            guess("1.0.0-1.20.1"),
+            ("1.0.0".into(), mc("1.20.1"), None, Confidence::Medium)
+        );
+        assert_eq!(
+            guess("1.4.2-1.20.1"),
+            ("1.4.2".into(), mc("1.20.1"), None, Confidence::Medium)
+        );
+        assert_eq!(
+            guess("1.20.1-1.4.2"),
+            ("1.4.2".into(), mc("1.20.1"), None, Confidence::Medium)
+        );
+        assert_eq!(
+            guess("1.0.0+build.45"),
+            ("1.0.0+build.45".into(), None, None, Confidence::Low)
+        );
+        assert_eq!(
+            guess("2.0+23w45a-neoforge"),
+            (
+                "2.0".into(),
+                mc("23w45a"),
+                Some(Loader::NeoForge),
+                Confidence::High
+            )
+        );
+        assert_eq!(
+            guess("1.2.3"),
+            ("1.2.3".into(), None, None, Confidence::Low)
+        );
+
+        let version = FabricVersion::parse("0.92.0+1.20.1", false).unwrap();
+        assert_eq!(from_fabric(&version).unwrap().minecraft, mc("1.20.1"));
+        let version = ForgeVersion::parse("1.20.1-47.1.0").unwrap();
+        assert_eq!(from_forge(&version).unwrap().mod_version, "47.1.0");
+        assert_eq!(from_version("beta"), None);
+    }
+
+    #[test]
+    fn guess_from_file_name() {
+        let mc = |s: &str| Some(s.to_string());
+
+        assert_eq!(
+            guess("sodium-fabric-0.5.3+mc1.20.1.jar"),
+            (
+                "0.5.3".into(),
+                mc("1.20.1"),
+                Some(Loader::Fabric),
+                Confidence::High
+            )
+        );
+        assert_eq!(
+            guess("lithium-fabric-mc1.20.1-0.11.2.jar"),
+            (
+                "0.11.2".into(),
+                mc("1.20.1"),
+                Some(Loader::Fabric),
+                Confidence::High
+            )
+        );
+        assert_eq!(
+            guess("jei-1.20.1-forge-15.2.0.27.jar"),
+            (
+                "15.2.0.27".into(),
+                mc("1.20.1"),
+                Some(Loader::Forge),
+                Confidence::Medium
+            )
+        );
+        assert_eq!(
+            guess("modmenu-7.2.2.jar"),
+            ("7.2.2".into(), None, None, Confidence::Low)
+        );
+        assert_eq!(
+            guess("mc2discord_v2.1.jar"),
+            ("v2.1".into(), None, None, Confidence::Low)
+        );
+        assert_eq!(from_file_name("optifine.jar"), None);
+    }
+}
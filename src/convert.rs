@@ -0,0 +1,317 @@
+use std::fmt;
+
+use crate::{
+    fabric::version::{
+        FabricVersion, FabricVersionIn, FabricVersionPredicate, FabricVersionPredicateIn,
+        FabricVersionRange, FabricVersionTerm, SemVer,
+    },
+    forge::version::{Bound, ForgeVersion, ForgeVersionRange, ForgeVersionRangeIn},
+};
+
+/// A range converted to another loader's syntax,
+/// with every place where it does not match exactly the same versions.
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Converted<T> {
+    pub range: T,
+    pub approximations: Vec<Approximation>,
+}
+
+impl<T> Converted<T> {
+    pub fn is_exact(&self) -> bool {
+        self.approximations.is_empty()
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub enum Approximation {
+    /// The version has a prerelease, qualifier or build metadata, which the loaders order differently,
+    /// e.g. `^1.2` excludes `2.0.0-beta` but `[1.2,2-)` includes it.
+    Qualifier(String),
+    /// The version is not SemVer, Fabric only matches it exactly and a bound on it is left out.
+    NotSemVer(String),
+}
+
+impl fmt::Display for Approximation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Approximation::Qualifier(version) => {
+                write!(f, "`{version}` has a qualifier that is ordered differently")
+            }
+            Approximation::NotSemVer(version) => write!(f, "`{version}` is not a SemVer version"),
+        }
+    }
+}
+
+impl FabricVersionRange {
+    /// Convert to a Maven range, e.g. `>=1.2 <2` is `[1.2,2)` and `^1.2` is `[1.2,2-)`.
+    /// Predicates that can never match are left out.
+    pub fn to_forge(&self) -> Converted<ForgeVersionRange> {
+        let mut approximations = vec![];
+        let ranges = self
+            .0
+            .iter()
+            .filter_map(|predicate| predicate_to_forge(predicate, &mut approximations))
+            .collect();
+
+        Converted {
+            range: ForgeVersionRange(ranges),
+            approximations,
+        }
+    }
+}
+
+fn predicate_to_forge(
+    predicate: &FabricVersionPredicate,
+    approximations: &mut Vec<Approximation>,
+) -> Option<ForgeVersionRangeIn> {
+    let Some(terms) = predicate.terms() else {
+        return Some(ForgeVersionRangeIn::Range(
+            Bound::Exclusive(None),
+            Bound::Exclusive(None),
+        ));
+    };
+    if !predicate.is_satisfiable() {
+        return None;
+    }
+
+    // A satisfiable predicate with a string version only has that version,
+    // it is left out when Maven has no such version, e.g. `=` is an empty string version.
+    if let Some(FabricVersionTerm::Equal(version)) = terms.first()
+        && let Some(s) = version.as_string()
+    {
+        approximations.push(Approximation::NotSemVer(s.to_string()));
+        return ForgeVersion::parse(s).ok().map(ForgeVersionRangeIn::Equal);
+    }
+
+    let lower = predicate.lower_bound();
+    let upper = predicate.upper_bound();
+
+    if let (Some((lo, true)), Some((hi, true))) = (&lower, &upper)
+        && lo == hi
+    {
+        let version = semver_to_forge(lo.clone(), approximations);
+        return Some(ForgeVersionRangeIn::Equal(version));
+    }
+
+    let mut bound = |bound: Option<(SemVer, bool)>| match bound {
+        Some((version, inclusive)) => {
+            Bound::from_bool(inclusive, Some(semver_to_forge(version, approximations)))
+        }
+        None => Bound::Exclusive(None),
+    };
+
+    Some(ForgeVersionRangeIn::Range(bound(lower), bound(upper)))
+}
+
+/// Build metadata does not affect Fabric's ordering, so it is dropped.
+fn semver_to_forge(version: SemVer, approximations: &mut Vec<Approximation>) -> ForgeVersion {
+    let s = version.without_build().to_string();
+    if version.is_prerelease() {
+        approximations.push(Approximation::Qualifier(s.clone()));
+    }
+    ForgeVersion::parse(&s).expect("a SemVer version always has a component")
+}
+
+impl ForgeVersionRange {
+    /// Convert to a Fabric range, e.g. `[1.2,2)` is `>=1.2 <2` and `(,1.0],[1.2,)` is `<=1.0|>=1.2`.
+    pub fn to_fabric(&self) -> Converted<FabricVersionRange> {
+        let mut approximations = vec![];
+        let predicates = self
+            .0
+            .iter()
+            .map(|range| range_to_fabric(range, &mut approximations))
+            .collect();
+
+        Converted {
+            range: FabricVersionRange(predicates),
+            approximations,
+        }
+    }
+}
+
+fn range_to_fabric(
+    range: &ForgeVersionRangeIn,
+    approximations: &mut Vec<Approximation>,
+) -> FabricVersionPredicate {
+    let mut terms = vec![];
+
+    match range {
+        ForgeVersionRangeIn::Equal(version) => {
+            let s = version.to_string();
+            let version = FabricVersion::parse(&s, false)
+                .unwrap_or_else(|_| FabricVersion(FabricVersionIn::String(s.clone())));
+            match &version.0 {
+                FabricVersionIn::SemVer(semver) if has_qualifier(semver) => {
+                    approximations.push(Approximation::Qualifier(s));
+                }
+                FabricVersionIn::SemVer(_) => {}
+                FabricVersionIn::String(_) => approximations.push(Approximation::NotSemVer(s)),
+            }
+            terms.push(FabricVersionTerm::Equal(version));
+        }
+        ForgeVersionRangeIn::Range(lower, upper) => {
+            let lower = bound_to_fabric(
+                lower,
+                FabricVersionTerm::GreaterOrEqual,
+                FabricVersionTerm::Greater,
+                approximations,
+            );
+            let upper = bound_to_fabric(
+                upper,
+                FabricVersionTerm::LessOrEqual,
+                FabricVersionTerm::Less,
+                approximations,
+            );
+            terms.extend(lower.into_iter().chain(upper));
+        }
+    }
+
+    if terms.is_empty() {
+        FabricVersionPredicate(FabricVersionPredicateIn::Any)
+    } else {
+        FabricVersionPredicate(FabricVersionPredicateIn::Terms(terms))
+    }
+}
+
+/// A bound on a version that is not SemVer is left out, which makes the range wider.
+fn bound_to_fabric(
+    bound: &Bound,
+    inclusive: fn(SemVer) -> FabricVersionTerm,
+    exclusive: fn(SemVer) -> FabricVersionTerm,
+    approximations: &mut Vec<Approximation>,
+) -> Option<FabricVersionTerm> {
+    let (version, op) = match bound {
+        Bound::Inclusive(Some(version)) => (version, inclusive),
+        Bound::Exclusive(Some(version)) => (version, exclusive),
+        Bound::Inclusive(None) | Bound::Exclusive(None) => return None,
+    };
+
+    let s = version.to_string();
+    match SemVer::parse(&s, false) {
+        Ok(semver) => {
+            if has_qualifier(&semver) {
+                approximations.push(Approximation::Qualifier(s));
+            }
+            Some(op(semver))
+        }
+        Err(_) => {
+            approximations.push(Approximation::NotSemVer(s));
+            None
+        }
+    }
+}
+
+fn has_qualifier(version: &SemVer) -> bool {
+    version.is_prerelease() || version.build().is_some()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn to_forge(s: &str) -> (String, Vec<Approximation>) {
+        let converted = FabricVersionRange::parse_many(s.split('|'))
+            .unwrap()
+            .to_forge();
+        (converted.range.to_string(), converted.approximations)
+    }
+
+    fn to_fabric(s: &str) -> (String, Vec<Approximation>) {
+        let converted = ForgeVersionRange::parse(s).unwrap().to_fabric();
+        (converted.range.to_string(), converted.approximations)
+    }
+
+    #[test]
+    fn fabric_to_forge() {
+        assert_eq!(to_forge(">=1.2 <2"), ("[1.2,2)".into(), vec![]));
+        assert_eq!(to_forge(">1.2 <=2"), ("(1.2,2]".into(), vec![]));
+        assert_eq!(to_forge("=1.2|>=3"), ("[1.2],[3,)".into(), vec![]));
+        assert_eq!(to_forge("<=1 >=1"), ("[1]".into(), vec![]));
+        assert_eq!(to_forge("*"), ("(,)".into(), vec![]));
+        assert_eq!(to_forge(">=2 <1"), ("".into(), vec![]));
+        assert_eq!(to_forge(">=1.0+build"), ("[1.0,)".into(), vec![]));
+        assert_eq!(
+            to_forge("^1.2"),
+            (
+                "[1.2,2-)".into(),
+                vec![Approximation::Qualifier("2-".into())]
+            )
+        );
+        assert_eq!(
+            to_forge("1.x"),
+            (
+                "[1-,2-)".into(),
+                vec![
+                    Approximation::Qualifier("1-".into()),
+                    Approximation::Qualifier("2-".into())
+                ]
+            )
+        );
+        assert_eq!(
+            to_forge("=abc"),
+            ("[abc]".into(), vec![Approximation::NotSemVer("abc".into())])
+        );
+        assert_eq!(
+            to_forge("="),
+            ("".into(), vec![Approximation::NotSemVer("".into())])
+        );
+
+        let empty = FabricVersion::parse("", false).unwrap();
+        let converted = FabricVersionRange::only(empty).to_forge();
+        assert_eq!(converted.range, ForgeVersionRange(vec![]));
+        assert_eq!(
+            converted.approximations,
+            vec![Approximation::NotSemVer("".into())]
+        );
+    }
+
+    #[test]
+    fn forge_to_fabric() {
+        assert_eq!(to_fabric("[1.2,2)"), (">=1.2 <2".into(), vec![]));
+        assert_eq!(to_fabric("(,1.0],[1.2,)"), ("<=1.0|>=1.2".into(), vec![]));
+        assert_eq!(to_fabric("[1.0]"), ("=1.0".into(), vec![]));
+        assert_eq!(to_fabric("(,)"), ("*".into(), vec![]));
+        assert_eq!(to_fabric(""), ("".into(), vec![]));
+        assert_eq!(
+            to_fabric("[1.2,2-)"),
+            (
+                ">=1.2 <2-".into(),
+                vec![Approximation::Qualifier("2-".into())]
+            )
+        );
+        assert_eq!(
+            to_fabric("[1.20.1-47.1.0,)"),
+            (
+                ">=1.20.1-47.1.0".into(),
+                vec![Approximation::Qualifier("1.20.1-47.1.0".into())]
+            )
+        );
+        assert_eq!(
+            to_fabric("[1.0.final,2)"),
+            (
+                "<2".into(),
+                vec![Approximation::NotSemVer("1.0.final".into())]
+            )
+        );
+        assert_eq!(
+            to_fabric("[abc]"),
+            ("=abc".into(), vec![Approximation::NotSemVer("abc".into())])
+        );
+    }
+
+    #[test]
+    fn round_trip() {
+        for s in [">=1.2 <2", "=1.2|>=3", "^1.2"] {
+            let range = FabricVersionRange::parse_many(s.split('|')).unwrap();
+            let back = range.to_forge().range.to_fabric().range;
+            for version in ["1.1", "1.2", "1.5", "2", "2.0.0-beta", "3"] {
+                let version = FabricVersion::parse(version, false).unwrap();
+                assert_eq!(
+                    range.matches(&version),
+                    back.matches(&version),
+                    "{s} {version}"
+                );
+            }
+        }
+    }
+}
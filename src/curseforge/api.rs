@@ -0,0 +1,384 @@
+use std::cmp;
+
+use serde::{Deserialize, Serialize};
+
+/// Every CurseForge Core API response wraps its result in `data`.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone)]
+pub struct Response<T> {
+    pub data: T,
+}
+
+/// A mod from `GET /v1/mods/{modId}`.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Mod {
+    pub id: u64,
+    pub game_id: u64,
+    pub name: String,
+    pub slug: String,
+    pub summary: String,
+    #[serde(default)]
+    pub download_count: u64,
+    pub main_file_id: u64,
+    #[serde(default)]
+    pub latest_files: Vec<File>,
+    #[serde(default)]
+    pub latest_files_indexes: Vec<FileIndex>,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct FileIndex {
+    pub game_version: String,
+    pub file_id: u64,
+    pub filename: String,
+    pub release_type: ReleaseType,
+    pub mod_loader: Option<u64>,
+}
+
+/// A file from `GET /v1/mods/{modId}/files/{fileId}`.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct File {
+    pub id: u64,
+    pub game_id: u64,
+    pub mod_id: u64,
+    pub is_available: bool,
+    pub display_name: String,
+    pub file_name: String,
+    pub release_type: ReleaseType,
+    pub file_date: String,
+    pub file_length: u64,
+    #[serde(default)]
+    pub download_count: u64,
+    /// `null` when the author disabled third party downloads.
+    pub download_url: Option<String>,
+    #[serde(default)]
+    pub hashes: Vec<FileHash>,
+    /// Minecraft versions, but also loaders such as `Fabric` and sides such as `Client`.
+    #[serde(default)]
+    pub game_versions: Vec<String>,
+    #[serde(default)]
+    pub dependencies: Vec<FileDependency>,
+    /// The Murmur2 fingerprint of the file.
+    pub file_fingerprint: u32,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone)]
+pub struct FileHash {
+    pub value: String,
+    pub algo: HashAlgo,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone, Copy)]
+#[serde(try_from = "u8", into = "u8")]
+pub enum HashAlgo {
+    Sha1 = 1,
+    Md5 = 2,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct FileDependency {
+    pub mod_id: u64,
+    pub relation_type: RelationType,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone, Copy)]
+#[serde(try_from = "u8", into = "u8")]
+pub enum RelationType {
+    EmbeddedLibrary = 1,
+    OptionalDependency = 2,
+    RequiredDependency = 3,
+    Tool = 4,
+    Incompatible = 5,
+    Include = 6,
+}
+
+/// Compared by stability, `Alpha < Beta < Release`.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone, Copy)]
+#[serde(try_from = "u8", into = "u8")]
+pub enum ReleaseType {
+    Release = 1,
+    Beta = 2,
+    Alpha = 3,
+}
+
+/// The result of `POST /v1/fingerprints`.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct FingerprintMatches {
+    pub is_cache_built: bool,
+    pub exact_matches: Vec<FingerprintMatch>,
+    pub exact_fingerprints: Vec<u32>,
+    #[serde(default)]
+    pub unmatched_fingerprints: Option<Vec<u32>>,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct FingerprintMatch {
+    /// The mod id.
+    pub id: u64,
+    pub file: File,
+    #[serde(default)]
+    pub latest_files: Vec<File>,
+}
+
+impl TryFrom<u8> for HashAlgo {
+    type Error = Error;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            1 => Ok(HashAlgo::Sha1),
+            2 => Ok(HashAlgo::Md5),
+            _ => Err(Error::InvalidHashAlgo(value)),
+        }
+    }
+}
+
+impl TryFrom<u8> for RelationType {
+    type Error = Error;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            1 => Ok(RelationType::EmbeddedLibrary),
+            2 => Ok(RelationType::OptionalDependency),
+            3 => Ok(RelationType::RequiredDependency),
+            4 => Ok(RelationType::Tool),
+            5 => Ok(RelationType::Incompatible),
+            6 => Ok(RelationType::Include),
+            _ => Err(Error::InvalidRelationType(value)),
+        }
+    }
+}
+
+impl TryFrom<u8> for ReleaseType {
+    type Error = Error;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            1 => Ok(ReleaseType::Release),
+            2 => Ok(ReleaseType::Beta),
+            3 => Ok(ReleaseType::Alpha),
+            _ => Err(Error::InvalidReleaseType(value)),
+        }
+    }
+}
+
+impl From<HashAlgo> for u8 {
+    fn from(value: HashAlgo) -> Self {
+        value as u8
+    }
+}
+
+impl From<RelationType> for u8 {
+    fn from(value: RelationType) -> Self {
+        value as u8
+    }
+}
+
+impl From<ReleaseType> for u8 {
+    fn from(value: ReleaseType) -> Self {
+        value as u8
+    }
+}
+
+impl Ord for ReleaseType {
+    fn cmp(&self, other: &Self) -> cmp::Ordering {
+        // The ids go from most to least stable.
+        (*other as u8).cmp(&(*self as u8))
+    }
+}
+
+impl PartialOrd for ReleaseType {
+    fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl File {
+    pub fn hash(&self, algo: HashAlgo) -> Option<&str> {
+        self.hashes
+            .iter()
+            .find(|hash| hash.algo == algo)
+            .map(|hash| hash.value.as_str())
+    }
+
+    /// Game versions are compared case-insensitively as CurseForge capitalizes loader names.
+    pub fn supports(&self, loader: &str, game_version: &str) -> bool {
+        let has = |s: &str| self.game_versions.iter().any(|v| v.eq_ignore_ascii_case(s));
+        has(loader) && has(game_version)
+    }
+}
+
+/// Pick the newest available file that supports the loader and Minecraft version,
+/// ignoring files less stable than `least_stable`.
+pub fn select_file<'a>(
+    files: &'a [File],
+    loader: &str,
+    game_version: &str,
+    least_stable: ReleaseType,
+) -> Option<&'a File> {
+    files
+        .iter()
+        .filter(|file| file.is_available && file.release_type >= least_stable)
+        .filter(|file| file.supports(loader, game_version))
+        .max_by(|a, b| a.file_date.cmp(&b.file_date).then(a.id.cmp(&b.id)))
+}
+
+/// Find the file with a Murmur2 fingerprint.
+pub fn find_by_fingerprint(files: &[File], fingerprint: u32) -> Option<&File> {
+    files
+        .iter()
+        .find(|file| file.file_fingerprint == fingerprint)
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("unknown hash algorithm {0}")]
+    InvalidHashAlgo(u8),
+    #[error("unknown relation type {0}")]
+    InvalidRelationType(u8),
+    #[error("unknown release type {0}")]
+    InvalidReleaseType(u8),
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_file() {
+        let file: File = serde_json::from_str(
+            r#"{
+                "id": 1,
+                "gameId": 432,
+                "modId": 394468,
+                "isAvailable": true,
+                "displayName": "Sodium 1",
+                "fileName": "sodium-1.jar",
+                "releaseType": 1,
+                "fileStatus": 4,
+                "hashes": [
+                    { "value": "da39a3ee5e6b4b0d3255bfef95601890afd80709", "algo": 1 },
+                    { "value": "d41d8cd98f00b204e9800998ecf8427e", "algo": 2 }
+                ],
+                "fileDate": "2024-01-01T00:00:00Z",
+                "fileLength": 1000,
+                "downloadCount": 10,
+                "downloadUrl": null,
+                "gameVersions": ["1.20.1", "Fabric"],
+                "dependencies": [{ "modId": 306612, "relationType": 3 }],
+                "fileFingerprint": 1
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            file.hash(HashAlgo::Md5),
+            Some("d41d8cd98f00b204e9800998ecf8427e")
+        );
+        assert_eq!(
+            file.dependencies[0].relation_type,
+            RelationType::RequiredDependency
+        );
+        assert_eq!(file.download_url, None);
+        assert_eq!(serde_json::to_value(file.release_type).unwrap(), 1);
+        assert!(serde_json::from_str::<ReleaseType>("4").is_err());
+
+        let response: Response<Mod> = serde_json::from_str(
+            r#"{ "data": {
+                "id": 394468,
+                "gameId": 432,
+                "name": "Sodium",
+                "slug": "sodium",
+                "summary": "Rendering engine",
+                "mainFileId": 1,
+                "latestFilesIndexes": [{
+                    "gameVersion": "1.20.1",
+                    "fileId": 1,
+                    "filename": "sodium-1.jar",
+                    "releaseType": 2,
+                    "modLoader": 4
+                }]
+            } }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            response.data.latest_files_indexes[0].release_type,
+            ReleaseType::Beta
+        );
+    }
+
+    #[test]
+    fn select_best_file() {
+        let files: Vec<File> = serde_json::from_str(
+            r#"[
+                {
+                    "id": 1,
+                    "gameId": 432,
+                    "modId": 394468,
+                    "isAvailable": true,
+                    "displayName": "Sodium 1",
+                    "fileName": "sodium-1.jar",
+                    "releaseType": 1,
+                    "fileDate": "2024-01-01T00:00:00Z",
+                    "fileLength": 1000,
+                    "gameVersions": ["1.20.1", "Fabric"],
+                    "fileFingerprint": 1
+                },
+                {
+                    "id": 2,
+                    "gameId": 432,
+                    "modId": 394468,
+                    "isAvailable": true,
+                    "displayName": "Sodium 2",
+                    "fileName": "sodium-2.jar",
+                    "releaseType": 2,
+                    "fileDate": "2024-02-01T00:00:00Z",
+                    "fileLength": 1000,
+                    "gameVersions": ["1.20.1", "Fabric", "Quilt"],
+                    "fileFingerprint": 2
+                },
+                {
+                    "id": 3,
+                    "gameId": 432,
+                    "modId": 394468,
+                    "isAvailable": true,
+                    "displayName": "Sodium 3",
+                    "fileName": "sodium-3.jar",
+                    "releaseType": 1,
+                    "fileDate": "2024-03-01T00:00:00Z",
+                    "fileLength": 1000,
+                    "gameVersions": ["1.20.1", "Forge"],
+                    "fileFingerprint": 3
+                },
+                {
+                    "id": 4,
+                    "gameId": 432,
+                    "modId": 394468,
+                    "isAvailable": true,
+                    "displayName": "Sodium 4",
+                    "fileName": "sodium-4.jar",
+                    "releaseType": 1,
+                    "fileDate": "2024-04-01T00:00:00Z",
+                    "fileLength": 1000,
+                    "gameVersions": ["1.20.4", "Fabric"],
+                    "fileFingerprint": 4
+                }
+            ]"#,
+        )
+        .unwrap();
+
+        let select = |loader, game_version, least_stable| {
+            select_file(&files, loader, game_version, least_stable).map(|file| file.id)
+        };
+        assert_eq!(select("fabric", "1.20.1", ReleaseType::Release), Some(1));
+        assert_eq!(select("fabric", "1.20.1", ReleaseType::Alpha), Some(2));
+        assert_eq!(select("forge", "1.20.1", ReleaseType::Release), Some(3));
+        assert_eq!(select("neoforge", "1.20.1", ReleaseType::Alpha), None);
+        assert!(ReleaseType::Alpha < ReleaseType::Release);
+
+        assert_eq!(find_by_fingerprint(&files, 3).map(|file| file.id), Some(3));
+    }
+}
@@ -0,0 +1,144 @@
+use std::{
+    fs,
+    io::{self, Read, Seek, SeekFrom},
+    path::Path,
+};
+
+const M: u32 = 0x5bd1e995;
+const SEED: u32 = 1;
+
+/// Bytes CurseForge removes before hashing: tab, line feed, carriage return and space.
+fn is_whitespace(b: u8) -> bool {
+    matches!(b, b'\t' | b'\n' | b'\r' | b' ')
+}
+
+/// Incremental Murmur2 hash of a file with its whitespace removed.
+/// Murmur2 mixes the length in first, so the number of non-whitespace bytes must be known upfront.
+pub struct Fingerprint {
+    h: u32,
+    buf: [u8; 4],
+    buf_len: usize,
+}
+
+impl Fingerprint {
+    pub fn new(normalized_len: u32) -> Self {
+        Self {
+            h: SEED ^ normalized_len,
+            buf: [0; 4],
+            buf_len: 0,
+        }
+    }
+
+    pub fn update(&mut self, bytes: &[u8]) {
+        for &b in bytes.iter().filter(|&&b| !is_whitespace(b)) {
+            self.buf[self.buf_len] = b;
+            self.buf_len += 1;
+
+            if self.buf_len == 4 {
+                let mut k = u32::from_le_bytes(self.buf);
+                k = k.wrapping_mul(M);
+                k ^= k >> 24;
+                k = k.wrapping_mul(M);
+                self.h = self.h.wrapping_mul(M) ^ k;
+                self.buf_len = 0;
+            }
+        }
+    }
+
+    pub fn finish(self) -> u32 {
+        let mut h = self.h;
+        let tail = &self.buf[..self.buf_len];
+        if tail.len() >= 3 {
+            h ^= (tail[2] as u32) << 16;
+        }
+        if tail.len() >= 2 {
+            h ^= (tail[1] as u32) << 8;
+        }
+        if let Some(&first) = tail.first() {
+            h ^= first as u32;
+            h = h.wrapping_mul(M);
+        }
+
+        h ^= h >> 13;
+        h = h.wrapping_mul(M);
+        h ^ (h >> 15)
+    }
+}
+
+/// The number of bytes that are hashed, CurseForge truncates it to 32 bits.
+fn normalized_len(bytes: &[u8]) -> u32 {
+    bytes.iter().filter(|&&b| !is_whitespace(b)).count() as u32
+}
+
+/// The CurseForge fingerprint of a file's contents.
+pub fn fingerprint(bytes: &[u8]) -> u32 {
+    let mut fingerprint = Fingerprint::new(normalized_len(bytes));
+    fingerprint.update(bytes);
+    fingerprint.finish()
+}
+
+/// The CurseForge fingerprint of everything from the current position of a reader,
+/// it is read twice so the contents never need to be in memory at once.
+pub fn fingerprint_reader<R: Read + Seek>(mut reader: R) -> io::Result<u32> {
+    let start = reader.stream_position()?;
+    let mut buf = vec![0; 64 * 1024];
+
+    let mut len = 0u32;
+    loop {
+        let n = reader.read(&mut buf)?;
+        if n == 0 {
+            break;
+        }
+        len = len.wrapping_add(normalized_len(&buf[..n]));
+    }
+
+    reader.seek(SeekFrom::Start(start))?;
+    let mut fingerprint = Fingerprint::new(len);
+    loop {
+        let n = reader.read(&mut buf)?;
+        if n == 0 {
+            break;
+        }
+        fingerprint.update(&buf[..n]);
+    }
+    Ok(fingerprint.finish())
+}
+
+/// The CurseForge fingerprint of a file, such as a mod jar.
+pub fn fingerprint_file(path: impl AsRef<Path>) -> io::Result<u32> {
+    fingerprint_reader(fs::File::open(path)?)
+}
+
+#[cfg(test)]
+mod test {
+    use std::io::Cursor;
+
+    use super::*;
+
+    #[test]
+    fn murmur2_fingerprint() {
+        assert_eq!(fingerprint(b""), 1540447798);
+        assert_eq!(fingerprint(b"a"), 626045324);
+        assert_eq!(fingerprint(b"hello world"), 2824650221);
+        assert_eq!(fingerprint(b"helloworld"), 2824650221);
+        assert_eq!(
+            fingerprint(b"The quick brown fox\njumps over\tthe lazy dog\r\n"),
+            3751777527
+        );
+    }
+
+    #[test]
+    fn streaming_fingerprint() {
+        let bytes: Vec<u8> = (0..=255).cycle().take(256 * 5).collect();
+        assert_eq!(fingerprint(&bytes), 2545890897);
+
+        // Chunks that do not line up with the 4 byte blocks.
+        let mut chunked = Fingerprint::new(normalized_len(&bytes));
+        for chunk in bytes.chunks(7) {
+            chunked.update(chunk);
+        }
+        assert_eq!(chunked.finish(), 2545890897);
+
+        assert_eq!(fingerprint_reader(Cursor::new(&bytes)).unwrap(), 2545890897);
+    }
+}
@@ -1,24 +1,32 @@
 use std::{collections::HashMap, fmt, path::PathBuf};
 
 use serde::{
-    Deserialize,
+    Deserialize, Serialize,
     de::{self, Error as _},
+    ser::SerializeSeq,
 };
 
 pub mod version;
 
 use version::FabricVersion;
 
-use crate::fabric::version::FabricVersionRange;
+use crate::{
+    fabric::version::{FabricVersionPredicate, FabricVersionRange},
+    lenient::ManifestWarning,
+    metadata::{self, ModIdError, Side},
+};
 
 /// A `fabric.mod.json` file.
 /// Contains metadata about a fabric mod.
-#[derive(Deserialize, PartialEq, Eq, Debug)]
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct FabricManifest {
     pub schema_version: SchemaVersion,
     pub id: ModId,
     pub name: Option<String>,
+    pub description: Option<String>,
+    #[serde(default)]
+    pub authors: Vec<Person>,
     pub version: FabricVersion,
     #[serde(default)]
     pub provides: Vec<ModId>,
@@ -26,38 +34,213 @@ pub struct FabricManifest {
     pub environment: Environment,
     #[serde(default)]
     pub jars: Vec<JarPath>,
+    /// Entrypoint type, such as `main` or `client`, mapped to the entrypoints of that type.
+    #[serde(default)]
+    pub entrypoints: HashMap<String, Vec<Entrypoint>>,
+    #[serde(default)]
+    pub mixins: Vec<MixinConfig>,
 
     #[serde(default)]
-    pub depends: HashMap<ModId, FabricVersionRange>,
+    pub depends: HashMap<String, FabricVersionRange>,
     #[serde(default)]
-    pub recommends: HashMap<ModId, FabricVersionRange>,
+    pub recommends: HashMap<String, FabricVersionRange>,
     #[serde(default)]
-    pub suggests: HashMap<ModId, FabricVersionRange>,
+    pub suggests: HashMap<String, FabricVersionRange>,
     #[serde(default)]
-    pub breaks: HashMap<ModId, FabricVersionRange>,
+    pub breaks: HashMap<String, FabricVersionRange>,
     #[serde(default)]
-    pub conflicts: HashMap<ModId, FabricVersionRange>,
+    pub conflicts: HashMap<String, FabricVersionRange>,
+}
+
+impl FabricManifest {
+    /// Parse a `fabric.mod.json`, normalizing versions and ranges that Fabric Loader accepts
+    /// but strict parsing rejects.
+    pub fn parse_lenient(s: &str) -> serde_json::Result<(Self, Vec<ManifestWarning>)> {
+        let mut value: serde_json::Value = serde_json::from_str(s)?;
+        let mut warnings = vec![];
+
+        if let Some(version) = value.get_mut("version")
+            && let Some(s) = version.as_str()
+            && let Ok((parsed, w)) = FabricVersion::parse_lenient(s, false)
+            && !w.is_empty()
+        {
+            *version = parsed.to_string().into();
+            warnings.extend(w.into_iter().map(|warning| ManifestWarning {
+                field: "version".into(),
+                warning,
+            }));
+        }
+
+        for key in ["depends", "recommends", "suggests", "breaks", "conflicts"] {
+            let Some(deps) = value.get_mut(key).and_then(|deps| deps.as_object_mut()) else {
+                continue;
+            };
+            for (id, range) in deps {
+                let predicates: Vec<&mut String> = match range {
+                    serde_json::Value::String(s) => vec![s],
+                    serde_json::Value::Array(items) => items
+                        .iter_mut()
+                        .filter_map(|item| match item {
+                            serde_json::Value::String(s) => Some(s),
+                            _ => None,
+                        })
+                        .collect(),
+                    _ => vec![],
+                };
+                for predicate in predicates {
+                    if let Ok((parsed, w)) = FabricVersionPredicate::parse_lenient(predicate)
+                        && !w.is_empty()
+                    {
+                        *predicate = parsed.to_string();
+                        warnings.extend(w.into_iter().map(|warning| ManifestWarning {
+                            field: format!("{key}.{id}"),
+                            warning,
+                        }));
+                    }
+                }
+            }
+        }
+
+        Ok((serde_json::from_value(value)?, warnings))
+    }
+}
+
+impl FabricManifest {
+    /// The mixin configs that are applied on `side`.
+    pub fn mixins_on(&self, side: Side) -> impl Iterator<Item = &MixinConfig> {
+        self.mixins
+            .iter()
+            .filter(move |mixin| mixin.environment().applies_to(side))
+    }
+
+    /// The entrypoints that are called on `side`, with their type.
+    /// Only `client` and `server` entrypoints are specific to a side.
+    pub fn entrypoints_on(&self, side: Side) -> impl Iterator<Item = (&str, &Entrypoint)> {
+        self.entrypoints
+            .iter()
+            .filter(move |(kind, _)| {
+                let kind_side = match kind.as_str() {
+                    "client" => Side::Client,
+                    "server" => Side::Server,
+                    _ => Side::Both,
+                };
+                kind_side.applies_to(side)
+            })
+            .flat_map(|(kind, entrypoints)| {
+                entrypoints
+                    .iter()
+                    .map(|entrypoint| (kind.as_str(), entrypoint))
+            })
+    }
+}
+
+/// An author or contributor, either just a name or a name with contact information.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone)]
+#[serde(untagged)]
+pub enum Person {
+    Name(String),
+    Contact {
+        name: String,
+        #[serde(default)]
+        contact: HashMap<String, String>,
+    },
 }
 
-#[derive(Deserialize, PartialEq, Eq, Debug)]
+impl Person {
+    pub fn name(&self) -> &str {
+        match self {
+            Person::Name(name) | Person::Contact { name, .. } => name,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug)]
 pub struct JarPath {
     pub file: PathBuf,
 }
 
-#[derive(Deserialize, PartialEq, Eq, Debug, Default)]
+/// A class, method or field to call, with an optional language adapter.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone)]
+#[serde(untagged)]
+pub enum Entrypoint {
+    Value(String),
+    Object {
+        adapter: Option<String>,
+        value: String,
+    },
+}
+
+impl Entrypoint {
+    pub fn value(&self) -> &str {
+        match self {
+            Entrypoint::Value(value) | Entrypoint::Object { value, .. } => value,
+        }
+    }
+}
+
+/// A mixin config file, optionally only applied in one environment.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone)]
+#[serde(untagged)]
+pub enum MixinConfig {
+    Path(String),
+    Object {
+        config: String,
+        #[serde(default)]
+        environment: Environment,
+    },
+}
+
+impl MixinConfig {
+    pub fn config(&self) -> &str {
+        match self {
+            MixinConfig::Path(config) | MixinConfig::Object { config, .. } => config,
+        }
+    }
+
+    pub fn environment(&self) -> Environment {
+        match self {
+            MixinConfig::Path(_) => Environment::Any,
+            MixinConfig::Object { environment, .. } => *environment,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone, Copy, Default)]
 #[serde(rename_all = "lowercase")]
 pub enum Environment {
     Client,
     Server,
-    #[serde(alias = "*")]
+    #[serde(alias = "*", rename(serialize = "*"))]
     #[default]
     Any,
 }
 
-#[derive(Deserialize, PartialEq, Eq, Debug)]
-#[serde(try_from = "u64")]
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone, Copy)]
+#[serde(try_from = "u64", into = "u64")]
 pub struct SchemaVersion;
 
+impl Environment {
+    pub fn applies_to(self, side: Side) -> bool {
+        Side::from(self).applies_to(side)
+    }
+}
+
+impl From<Environment> for Side {
+    fn from(value: Environment) -> Self {
+        match value {
+            Environment::Client => Side::Client,
+            Environment::Server => Side::Server,
+            Environment::Any => Side::Both,
+        }
+    }
+}
+
+impl From<SchemaVersion> for u64 {
+    fn from(_: SchemaVersion) -> Self {
+        1
+    }
+}
+
 impl TryFrom<u64> for SchemaVersion {
     type Error = Error;
 
@@ -70,19 +253,23 @@ impl TryFrom<u64> for SchemaVersion {
     }
 }
 
-#[derive(Deserialize, PartialEq, Eq, Debug, Hash, Clone)]
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Hash, Clone)]
 #[serde(try_from = "String")]
 pub struct ModId(pub String);
 
+impl ModId {
+    /// Check that an id matches `^[a-z][a-z0-9-_]{1,63}$`.
+    pub fn validate(id: &str) -> Result<(), ModIdError> {
+        metadata::validate_mod_id(id, &['-', '_'])
+    }
+}
+
 impl TryFrom<String> for ModId {
     type Error = Error;
 
     fn try_from(value: String) -> Result<Self, Self::Error> {
-        if (2..=64).contains(&value.len()) {
-            Ok(ModId(value.to_string()))
-        } else {
-            Err(Error::ModIdTooLong)
-        }
+        Self::validate(&value)?;
+        Ok(ModId(value))
     }
 }
 
@@ -129,6 +316,23 @@ impl<'de> Deserialize<'de> for FabricVersionRange {
     }
 }
 
+impl Serialize for FabricVersionRange {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        if let [predicate] = self.predicates() {
+            return serializer.collect_str(predicate);
+        }
+
+        let mut seq = serializer.serialize_seq(Some(self.predicates().len()))?;
+        for predicate in self.predicates() {
+            seq.serialize_element(&predicate.to_string())?;
+        }
+        seq.end()
+    }
+}
+
 impl<'de> Deserialize<'de> for FabricVersion {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -155,10 +359,19 @@ impl<'de> Deserialize<'de> for FabricVersion {
     }
 }
 
+impl Serialize for FabricVersion {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
 #[derive(thiserror::Error, Debug)]
 pub enum Error {
-    #[error("mod id must be between 2-64 characters")]
-    ModIdTooLong,
+    #[error(transparent)]
+    ModId(#[from] ModIdError),
     #[error("invalid schema version is not 1")]
     InvalidSchemaVersion,
 }
@@ -166,6 +379,7 @@ pub enum Error {
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::metadata::ModMetadata;
 
     #[test]
     fn parse_mod_json() {
@@ -175,6 +389,7 @@ mod test {
             "version": "1.0.0-pre+build",
             "name": "Example Mod",
             "description": "Lorem ipsum dolor sit amet.",
+            "authors": ["Me", { "name": "You", "contact": { "homepage": "https://example.com" } }],
             "environment": "client",
             "depends": {
                 "minecraft": [
@@ -198,14 +413,24 @@ mod test {
                 schema_version: SchemaVersion,
                 id: ModId("examplemod".into()),
                 name: Some("Example Mod".into()),
+                description: Some("Lorem ipsum dolor sit amet.".into()),
+                authors: vec![
+                    Person::Name("Me".into()),
+                    Person::Contact {
+                        name: "You".into(),
+                        contact: HashMap::from([("homepage".into(), "https://example.com".into())]),
+                    }
+                ],
                 version: FabricVersion::parse("1.0.0-pre+build", false).unwrap(),
                 provides: vec![],
                 environment: Environment::Client,
                 jars: vec![JarPath {
                     file: "META-INF/jars/example.jar".into()
                 }],
+                entrypoints: Default::default(),
+                mixins: vec![],
                 depends: HashMap::from([(
-                    ModId("minecraft".into()),
+                    "minecraft".into(),
                     FabricVersionRange::parse_many(["1.21", "1.20.1"].iter()).unwrap()
                 )]),
                 recommends: Default::default(),
@@ -215,4 +440,88 @@ mod test {
             }
         )
     }
+
+    #[test]
+    fn filter_by_side() {
+        let manifest: FabricManifest = serde_json::from_str(
+            r#"{
+                "schemaVersion": 1,
+                "id": "examplemod",
+                "version": "1.0.0",
+                "entrypoints": {
+                    "main": ["com.example.Main"],
+                    "client": [{ "adapter": "kotlin", "value": "com.example.Client" }]
+                },
+                "mixins": [
+                    "example.mixins.json",
+                    { "config": "example.client.mixins.json", "environment": "client" }
+                ]
+            }"#,
+        )
+        .unwrap();
+        assert!(manifest.loads_on(Side::Server));
+
+        let mixins: Vec<_> = manifest
+            .mixins_on(Side::Server)
+            .map(|m| m.config())
+            .collect();
+        assert_eq!(mixins, vec!["example.mixins.json"]);
+        assert_eq!(manifest.mixins_on(Side::Client).count(), 2);
+
+        let entrypoints: Vec<_> = manifest
+            .entrypoints_on(Side::Server)
+            .map(|(kind, entrypoint)| (kind, entrypoint.value()))
+            .collect();
+        assert_eq!(entrypoints, vec![("main", "com.example.Main")]);
+        assert_eq!(manifest.entrypoints_on(Side::Client).count(), 2);
+
+        assert!(!Environment::Client.applies_to(Side::Server));
+        assert!(Environment::Client.applies_to(Side::Both));
+        assert!(Environment::Any.applies_to(Side::Client));
+    }
+
+    #[test]
+    fn parse_mod_json_lenient() {
+        let s = r#"{
+            "schemaVersion": 1,
+            "id": "examplemod",
+            "version": "v1.0.0",
+            "depends": { "minecraft": ["1.20.1", ">=1.21  <1.22"] }
+        }"#;
+        assert!(serde_json::from_str::<FabricManifest>(s).is_err());
+
+        let (manifest, warnings) = FabricManifest::parse_lenient(s).unwrap();
+        assert_eq!(manifest.version.to_string(), "1.0.0");
+        assert_eq!(
+            manifest.depends["minecraft"].to_string(),
+            "=1.20.1|>=1.21 <1.22"
+        );
+        let warnings: Vec<_> = warnings.iter().map(ToString::to_string).collect();
+        assert_eq!(
+            warnings,
+            vec![
+                "version: leading `v` before the version at `v` (offset 0)",
+                "depends.minecraft: repeated space between terms at ` ` (offset 7)",
+            ]
+        );
+    }
+
+    #[test]
+    fn validate_mod_id() {
+        assert_eq!(ModId::validate("example-mod_2"), Ok(()));
+        assert_eq!(
+            ModId::validate("example.mod"),
+            Err(ModIdError::InvalidChar {
+                found: '.',
+                allowed: &['-', '_']
+            })
+        );
+        assert!(serde_json::from_str::<ModId>(r#""Example""#).is_err());
+
+        let manifest: FabricManifest = serde_json::from_str(
+            r#"{ "schemaVersion": 1, "id": "examplemod", "version": "1.0.0", "depends": { "ExampleLib": "*" } }"#,
+        )
+        .unwrap();
+        assert!(manifest.depends.contains_key("ExampleLib"));
+    }
 }
@@ -1,15 +1,29 @@
-use std::{cmp, fmt, num::ParseIntError};
+use std::{
+    cmp, fmt,
+    hash::{Hash, Hasher},
+    num::ParseIntError,
+};
+
+use crate::{
+    lenient::{self, Warning},
+    located::Located,
+};
 
 /// A list of predicates with an `OR` relationship,
 /// e.g a range that is 1 `OR` 2 is `["1", "2"]`
 #[derive(PartialEq, Eq, Debug, Clone)]
-pub struct FabricVersionRange(Vec<FabricVersionPredicate>);
+pub struct FabricVersionRange(pub(crate) Vec<FabricVersionPredicate>);
 
 impl FabricVersionRange {
     pub fn matches(&self, other: &FabricVersion) -> bool {
         self.0.iter().any(|pred| pred.matches(other))
     }
 
+    /// Check if there is any version that can match the range.
+    pub fn is_satisfiable(&self) -> bool {
+        self.0.iter().any(FabricVersionPredicate::is_satisfiable)
+    }
+
     pub fn empty() -> Self {
         Self(vec![])
     }
@@ -19,6 +33,81 @@ impl FabricVersionRange {
             FabricVersionPredicateIn::Terms(vec![FabricVersionTerm::Equal(version)]),
         )])
     }
+
+    pub fn predicates(&self) -> &[FabricVersionPredicate] {
+        &self.0
+    }
+
+    /// The lowest version any predicate allows and whether it is inclusive,
+    /// `None` if it is unbounded or nothing matches.
+    pub fn lower_bound(&self) -> Option<(SemVer, bool)> {
+        self.union_bound(FabricVersionPredicate::lower_bound, cmp::Ordering::Less)
+    }
+
+    /// The highest version any predicate allows and whether it is inclusive,
+    /// `None` if it is unbounded or nothing matches.
+    pub fn upper_bound(&self) -> Option<(SemVer, bool)> {
+        self.union_bound(FabricVersionPredicate::upper_bound, cmp::Ordering::Greater)
+    }
+
+    /// Pick the bound that is furthest in the direction of `order` out of every predicate that can match,
+    /// a bound is inclusive if any of the bounds are inclusive.
+    fn union_bound(
+        &self,
+        bound: fn(&FabricVersionPredicate) -> Option<(SemVer, bool)>,
+        order: cmp::Ordering,
+    ) -> Option<(SemVer, bool)> {
+        let mut result: Option<(SemVer, bool)> = None;
+
+        for predicate in self.0.iter().filter(|pred| pred.is_satisfiable()) {
+            let (version, inclusive) = bound(predicate)?;
+            result = Some(match result {
+                Some((current, current_inclusive)) => match version.cmp(&current) {
+                    cmp::Ordering::Equal => (current, current_inclusive || inclusive),
+                    ord if ord == order => (version, inclusive),
+                    _ => (current, current_inclusive),
+                },
+                None => (version, inclusive),
+            });
+        }
+
+        result
+    }
+}
+
+impl FabricVersionRange {
+    /// The versions that match the range.
+    /// Like npm and Cargo, prereleases are left out unless `include_prereleases` is set
+    /// or a term of the predicate has a prerelease of the same version, e.g. `>=1.2-beta`.
+    pub fn filter<'a>(
+        &self,
+        versions: impl IntoIterator<Item = &'a FabricVersion>,
+        include_prereleases: bool,
+    ) -> impl Iterator<Item = &'a FabricVersion> {
+        versions.into_iter().filter(move |version| {
+            self.0.iter().any(|pred| {
+                pred.matches(version) && (include_prereleases || pred.allows_prerelease(version))
+            })
+        })
+    }
+
+    /// The newest version that matches the range, see [`FabricVersionRange::filter`].
+    pub fn max_satisfying<'a>(
+        &self,
+        versions: impl IntoIterator<Item = &'a FabricVersion>,
+        include_prereleases: bool,
+    ) -> Option<&'a FabricVersion> {
+        self.filter(versions, include_prereleases).max()
+    }
+
+    /// The oldest version that matches the range, see [`FabricVersionRange::filter`].
+    pub fn min_satisfying<'a>(
+        &self,
+        versions: impl IntoIterator<Item = &'a FabricVersion>,
+        include_prereleases: bool,
+    ) -> Option<&'a FabricVersion> {
+        self.filter(versions, include_prereleases).min()
+    }
 }
 
 impl FabricVersionRange {
@@ -40,6 +129,27 @@ impl FabricVersionRange {
 
         Ok(Self(res?))
     }
+
+    /// Like [`FabricVersionRange::parse_single`], but accepts what Fabric Loader accepts,
+    /// see [`FabricVersionPredicate::parse_lenient`].
+    pub fn parse_single_lenient(s: &str) -> Result<(Self, Vec<Located<Warning>>)> {
+        let (predicate, warnings) = FabricVersionPredicate::parse_lenient(s)?;
+        Ok((Self(vec![predicate]), warnings))
+    }
+
+    pub fn parse_many_lenient<I: Iterator>(iter: I) -> Result<(Self, Vec<Located<Warning>>)>
+    where
+        I::Item: AsRef<str>,
+    {
+        let mut predicates = vec![];
+        let mut warnings = vec![];
+        for s in iter {
+            let (predicate, w) = FabricVersionPredicate::parse_lenient(s.as_ref())?;
+            predicates.push(predicate);
+            warnings.extend(w);
+        }
+        Ok((Self(predicates), warnings))
+    }
 }
 
 impl fmt::Display for FabricVersionRange {
@@ -58,10 +168,10 @@ impl fmt::Display for FabricVersionRange {
 /// Space separated terms with an `AND` relationship,
 /// e.g a predicate that is greater than or equal to 1 `AND` less than 2 is `>=1 <2`
 #[derive(PartialEq, Eq, Debug, Clone)]
-pub struct FabricVersionPredicate(FabricVersionPredicateIn);
+pub struct FabricVersionPredicate(pub(crate) FabricVersionPredicateIn);
 
 #[derive(PartialEq, Eq, Debug, Clone)]
-enum FabricVersionPredicateIn {
+pub(crate) enum FabricVersionPredicateIn {
     Terms(Vec<FabricVersionTerm>),
     Any,
 }
@@ -73,23 +183,184 @@ impl FabricVersionPredicate {
             FabricVersionPredicateIn::Any => true,
         }
     }
+
+    /// Check if there is any version that can match the predicate,
+    /// e.g `>=2 <1` can never match.
+    pub fn is_satisfiable(&self) -> bool {
+        let FabricVersionPredicateIn::Terms(terms) = &self.0 else {
+            return true;
+        };
+
+        let mut string = None;
+        let mut has_semver = false;
+
+        for term in terms {
+            if let FabricVersionTerm::Equal(FabricVersion(FabricVersionIn::String(s))) = term {
+                if string.is_some_and(|string| string != s) {
+                    return false;
+                }
+                string = Some(s);
+            } else {
+                has_semver = true;
+            }
+        }
+
+        // String versions are only matched by an equals term.
+        if string.is_some() && has_semver {
+            return false;
+        }
+
+        match self.bounds() {
+            (Some((lower, lower_inclusive)), Some((upper, upper_inclusive))) => {
+                match lower.cmp(&upper) {
+                    cmp::Ordering::Less => true,
+                    cmp::Ordering::Equal => lower_inclusive && upper_inclusive,
+                    cmp::Ordering::Greater => false,
+                }
+            }
+            _ => true,
+        }
+    }
+
+    /// The terms that must all match, `None` for `*`.
+    pub fn terms(&self) -> Option<&[FabricVersionTerm]> {
+        match &self.0 {
+            FabricVersionPredicateIn::Terms(terms) => Some(terms),
+            FabricVersionPredicateIn::Any => None,
+        }
+    }
+
+    /// The lowest version the predicate allows and whether it is inclusive,
+    /// `None` if it is unbounded.
+    /// String versions are not ordered against SemVer, so they do not bound a predicate.
+    pub fn lower_bound(&self) -> Option<(SemVer, bool)> {
+        self.bounds().0
+    }
+
+    /// The highest version the predicate allows and whether it is inclusive,
+    /// `None` if it is unbounded.
+    pub fn upper_bound(&self) -> Option<(SemVer, bool)> {
+        self.bounds().1
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn bounds(&self) -> (Option<(SemVer, bool)>, Option<(SemVer, bool)>) {
+        let mut lower = None;
+        let mut upper = None;
+
+        for term in self.terms().unwrap_or_default() {
+            let (lo, hi) = term.bounds();
+            if let Some(lo) = lo {
+                lower = Some(tighter(lower, lo, cmp::Ordering::Greater));
+            }
+            if let Some(hi) = hi {
+                upper = Some(tighter(upper, hi, cmp::Ordering::Less));
+            }
+        }
+
+        (lower, upper)
+    }
+}
+
+impl FabricVersionPredicate {
+    /// Check if the version is not a prerelease, or a term mentions a prerelease of the same version.
+    fn allows_prerelease(&self, version: &FabricVersion) -> bool {
+        let FabricVersion(FabricVersionIn::SemVer(version)) = version else {
+            return true;
+        };
+        if !version.is_prerelease() {
+            return true;
+        }
+
+        self.terms()
+            .unwrap_or_default()
+            .iter()
+            .filter_map(FabricVersionTerm::semver)
+            .any(|mentioned| mentioned.is_prerelease() && mentioned.key().0 == version.key().0)
+    }
+}
+
+/// Pick the bound that is furthest in the direction of `order`,
+/// a bound is only inclusive if both bounds are inclusive.
+fn tighter(
+    current: Option<(SemVer, bool)>,
+    (version, inclusive): (SemVer, bool),
+    order: cmp::Ordering,
+) -> (SemVer, bool) {
+    let Some((current, current_inclusive)) = current else {
+        return (version, inclusive);
+    };
+
+    match version.cmp(&current) {
+        cmp::Ordering::Equal => (current, current_inclusive && inclusive),
+        ord if ord == order => (version, inclusive),
+        _ => (current, current_inclusive),
+    }
 }
 
 impl FabricVersionPredicate {
-    pub fn parse(s: &str) -> Result<FabricVersionPredicate> {
-        let s = s.trim();
+    pub fn parse(input: &str) -> Result<FabricVersionPredicate> {
+        Self::parse_with(input, false).map(|(predicate, _)| predicate)
+    }
+
+    /// Like [`FabricVersionPredicate::parse`], but skips empty terms and `*` next to other terms
+    /// like Fabric Loader does, and allows a `v` before versions.
+    pub fn parse_lenient(input: &str) -> Result<(FabricVersionPredicate, Vec<Located<Warning>>)> {
+        Self::parse_with(input, true)
+    }
+
+    fn parse_with(
+        input: &str,
+        lenient: bool,
+    ) -> Result<(FabricVersionPredicate, Vec<Located<Warning>>)> {
+        let s = input.trim();
 
         if s == "*" {
-            return Ok(Self(FabricVersionPredicateIn::Any));
+            return Ok((Self(FabricVersionPredicateIn::Any), vec![]));
+        }
+        if lenient && s.is_empty() {
+            let warning = Located::new(Warning::EmptyPredicate, input, 0, input);
+            return Ok((Self(FabricVersionPredicateIn::Any), vec![warning]));
         }
 
         let mut terms = vec![];
+        let mut warnings = vec![];
+        let mut offset = input.len() - input.trim_start().len();
 
         for s in s.split(' ') {
-            terms.push(FabricVersionTerm::parse(s)?);
+            if lenient && s.is_empty() {
+                warnings.push(Located::new(Warning::RepeatedSpace, input, offset, " "));
+            } else if lenient && s == "*" {
+                warnings.push(Located::new(Warning::IgnoredWildcard, input, offset, s));
+            } else if s.is_empty() {
+                // Point at the extra space, the only empty term at the end is an empty input.
+                let token = if offset < input.len() { " " } else { "" };
+                return Err(Error::Located(Box::new(Located::new(
+                    Error::Empty,
+                    input,
+                    offset,
+                    token,
+                ))));
+            } else {
+                match FabricVersionTerm::parse(s, lenient) {
+                    Ok((term, leading_v)) => {
+                        if let Some(i) = leading_v {
+                            warnings.push(Located::new(Warning::LeadingV, input, offset + i, "v"));
+                        }
+                        terms.push(term);
+                    }
+                    Err(e) => {
+                        return Err(Error::Located(Box::new(Located::new(e, input, offset, s))));
+                    }
+                }
+            }
+            offset += s.len() + 1;
         }
 
-        Ok(Self(FabricVersionPredicateIn::Terms(terms)))
+        if terms.is_empty() {
+            return Ok((Self(FabricVersionPredicateIn::Any), warnings));
+        }
+        Ok((Self(FabricVersionPredicateIn::Terms(terms)), warnings))
     }
 }
 
@@ -114,7 +385,7 @@ impl fmt::Display for FabricVersionPredicate {
 /// A single term in a fabric version range.
 /// Such as `>=1.2.3` or `<4.0.0`
 #[derive(PartialEq, Eq, Debug, Clone)]
-enum FabricVersionTerm {
+pub enum FabricVersionTerm {
     Equal(FabricVersion),
     Greater(SemVer),
     GreaterOrEqual(SemVer),
@@ -125,6 +396,19 @@ enum FabricVersionTerm {
 }
 
 impl FabricVersionTerm {
+    /// The version the term compares against, `None` for a string version.
+    pub fn semver(&self) -> Option<&SemVer> {
+        match self {
+            FabricVersionTerm::Equal(version) => version.as_semver(),
+            FabricVersionTerm::Greater(version)
+            | FabricVersionTerm::GreaterOrEqual(version)
+            | FabricVersionTerm::Less(version)
+            | FabricVersionTerm::LessOrEqual(version)
+            | FabricVersionTerm::Caret(version)
+            | FabricVersionTerm::Tilde(version) => Some(version),
+        }
+    }
+
     pub fn matches(&self, version: &FabricVersion) -> bool {
         if let (
             FabricVersionTerm::Equal(FabricVersion(FabricVersionIn::String(range))),
@@ -140,27 +424,31 @@ impl FabricVersionTerm {
 
         match self {
             FabricVersionTerm::Equal(FabricVersion(FabricVersionIn::SemVer(range))) => {
-                version.cmp(range).is_eq()
+                version.cmp_matching(range).is_eq()
             }
-            FabricVersionTerm::Greater(range) => version.cmp(range).is_gt(),
-            FabricVersionTerm::GreaterOrEqual(range) => version.cmp(range).is_ge(),
-            FabricVersionTerm::Less(range) => version.cmp(range).is_lt(),
-            FabricVersionTerm::LessOrEqual(range) => version.cmp(range).is_le(),
+            FabricVersionTerm::Greater(range) => version.cmp_matching(range).is_gt(),
+            FabricVersionTerm::GreaterOrEqual(range) => version.cmp_matching(range).is_ge(),
+            FabricVersionTerm::Less(range) => version.cmp_matching(range).is_lt(),
+            FabricVersionTerm::LessOrEqual(range) => version.cmp_matching(range).is_le(),
             FabricVersionTerm::Caret(range) => {
-                if !version.cmp(range).is_ge() {
+                if !version.cmp_matching(range).is_ge() {
                     return false;
                 }
 
                 let mut range = range.clone();
-                version.cmp(range.inc_major().prerelease()).is_lt()
+                version
+                    .cmp_matching(range.inc_major().first_prerelease())
+                    .is_lt()
             }
             FabricVersionTerm::Tilde(range) => {
-                if !version.cmp(range).is_ge() {
+                if !version.cmp_matching(range).is_ge() {
                     return false;
                 }
 
                 let mut range = range.clone();
-                version.cmp(range.inc_minor().prerelease()).is_lt()
+                version
+                    .cmp_matching(range.inc_minor().first_prerelease())
+                    .is_lt()
             }
             _ => false,
         }
@@ -168,52 +456,106 @@ impl FabricVersionTerm {
 }
 
 impl FabricVersionTerm {
-    fn parse(s: &str) -> Result<FabricVersionTerm> {
-        if let Some(value) = Self::parse_eq(s, "=", FabricVersionTerm::Equal)? {
-            return Ok(value);
-        }
-        if let Some(value) = Self::parse_op(s, ">=", FabricVersionTerm::GreaterOrEqual)? {
-            return Ok(value);
-        }
-        if let Some(value) = Self::parse_op(s, ">", FabricVersionTerm::Greater)? {
-            return Ok(value);
-        }
-        if let Some(value) = Self::parse_op(s, "<=", FabricVersionTerm::LessOrEqual)? {
-            return Ok(value);
-        }
-        if let Some(value) = Self::parse_op(s, "<", FabricVersionTerm::Less)? {
-            return Ok(value);
-        }
-        if let Some(value) = Self::parse_op(s, "^", FabricVersionTerm::Caret)? {
-            return Ok(value);
-        }
-        if let Some(value) = Self::parse_op(s, "~", FabricVersionTerm::Tilde)? {
-            return Ok(value);
+    /// The lower and upper bound of the versions matched by this term
+    /// and whether the bound is inclusive, `None` if it is unbounded.
+    #[allow(clippy::type_complexity)]
+    fn bounds(&self) -> (Option<(SemVer, bool)>, Option<(SemVer, bool)>) {
+        let Some(version) = self.semver() else {
+            return (None, None);
+        };
+
+        if version.has_wildcard {
+            return self.wildcard_bounds(version);
         }
 
-        Ok(FabricVersionTerm::Equal(FabricVersion::parse(s, true)?))
-    }
+        let inclusive = |version: &SemVer| Some((version.clone(), true));
+        let exclusive = |version: &SemVer| Some((version.clone(), false));
 
-    fn parse_eq(
-        s: &str,
-        symbol: &str,
-        op: impl Fn(FabricVersion) -> FabricVersionTerm,
-    ) -> Result<Option<FabricVersionTerm>> {
-        match s.strip_prefix(symbol) {
-            Some(s) => Ok(Some(op(FabricVersion::parse(s, true)?))),
-            None => Ok(None),
+        match self {
+            FabricVersionTerm::Equal(_) => (inclusive(version), inclusive(version)),
+            FabricVersionTerm::Greater(_) => (exclusive(version), None),
+            FabricVersionTerm::GreaterOrEqual(_) => (inclusive(version), None),
+            FabricVersionTerm::Less(_) => (None, exclusive(version)),
+            FabricVersionTerm::LessOrEqual(_) => (None, inclusive(version)),
+            FabricVersionTerm::Caret(_) => (
+                inclusive(version),
+                exclusive(version.clone().inc_major().first_prerelease()),
+            ),
+            FabricVersionTerm::Tilde(_) => (
+                inclusive(version),
+                exclusive(version.clone().inc_minor().first_prerelease()),
+            ),
         }
     }
+}
 
-    fn parse_op(
-        s: &str,
-        symbol: &str,
-        op: impl Fn(SemVer) -> FabricVersionTerm,
-    ) -> Result<Option<FabricVersionTerm>> {
-        match s.strip_prefix(symbol) {
-            Some(s) => Ok(Some(op(SemVer::parse(s, true)?))),
-            None => Ok(None),
+impl FabricVersionTerm {
+    /// Every version a wildcard covers compares `Equal` to it, so it spans from the first
+    /// prerelease of its components to the first prerelease after them, e.g. `1.x` is `>=1- <2-`.
+    #[allow(clippy::type_complexity)]
+    fn wildcard_bounds(
+        &self,
+        version: &SemVer,
+    ) -> (Option<(SemVer, bool)>, Option<(SemVer, bool)>) {
+        let first_prerelease = |version: &SemVer| {
+            Some((
+                SemVer {
+                    components: version.components.clone(),
+                    prerelease: Some(vec![]),
+                    build: None,
+                    has_wildcard: false,
+                },
+                true,
+            ))
+        };
+        let before = |bound: Option<(SemVer, bool)>| bound.map(|(version, _)| (version, false));
+
+        let lower = first_prerelease(version);
+        let mut next = version.clone();
+        if let Some(last) = next.components.last_mut() {
+            *last += 1;
         }
+        let upper = first_prerelease(&next);
+
+        match self {
+            FabricVersionTerm::Equal(_) => (lower, before(upper)),
+            FabricVersionTerm::Greater(_) => (upper, None),
+            FabricVersionTerm::GreaterOrEqual(_) => (lower, None),
+            FabricVersionTerm::Less(_) => (None, before(lower)),
+            FabricVersionTerm::LessOrEqual(_) => (None, before(upper)),
+            FabricVersionTerm::Caret(_) => {
+                (lower, before(first_prerelease(version.clone().inc_major())))
+            }
+            FabricVersionTerm::Tilde(_) => {
+                (lower, before(first_prerelease(version.clone().inc_minor())))
+            }
+        }
+    }
+}
+
+impl FabricVersionTerm {
+    /// Parse a term, in lenient mode also returning the offset of a stripped `v`.
+    fn parse(s: &str, lenient: bool) -> Result<(FabricVersionTerm, Option<usize>)> {
+        let (symbol, version) = [">=", "<=", ">", "<", "=", "^", "~"]
+            .into_iter()
+            .find_map(|symbol| Some((symbol, s.strip_prefix(symbol)?)))
+            .unwrap_or(("", s));
+
+        let (version, leading_v) = match lenient.then(|| lenient::strip_v(version)).flatten() {
+            Some(version) => (version, Some(symbol.len())),
+            None => (version, None),
+        };
+
+        let term = match symbol {
+            ">=" => FabricVersionTerm::GreaterOrEqual(SemVer::parse(version, true)?),
+            "<=" => FabricVersionTerm::LessOrEqual(SemVer::parse(version, true)?),
+            ">" => FabricVersionTerm::Greater(SemVer::parse(version, true)?),
+            "<" => FabricVersionTerm::Less(SemVer::parse(version, true)?),
+            "^" => FabricVersionTerm::Caret(SemVer::parse(version, true)?),
+            "~" => FabricVersionTerm::Tilde(SemVer::parse(version, true)?),
+            _ => FabricVersionTerm::Equal(FabricVersion::parse(version, true)?),
+        };
+        Ok((term, leading_v))
     }
 }
 
@@ -232,11 +574,11 @@ impl fmt::Display for FabricVersionTerm {
 }
 
 /// A version used by Fabric mods.
-#[derive(PartialEq, Eq, Debug, Clone)]
-pub struct FabricVersion(FabricVersionIn);
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
+pub struct FabricVersion(pub(crate) FabricVersionIn);
 
-#[derive(PartialEq, Eq, Debug, Clone)]
-enum FabricVersionIn {
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
+pub(crate) enum FabricVersionIn {
     SemVer(SemVer),
     String(String),
 }
@@ -247,10 +589,48 @@ impl FabricVersion {
     pub fn parse(s: &str, allow_wildcards: bool) -> Result<Self> {
         match SemVer::parse(s, allow_wildcards) {
             Ok(value) => Ok(Self(FabricVersionIn::SemVer(value))),
-            Err(Error::Empty) => Ok(Self(FabricVersionIn::String(s.to_string()))),
+            Err(Error::Empty | Error::ParseInt(_)) => {
+                Ok(Self(FabricVersionIn::String(s.to_string())))
+            }
             Err(e) => Err(e),
         }
     }
+
+    /// Like [`FabricVersion::parse`], but a `v` before a semantic version is stripped
+    /// instead of making it a string version.
+    pub fn parse_lenient(s: &str, allow_wildcards: bool) -> Result<(Self, Vec<Located<Warning>>)> {
+        if let Ok((version, warnings)) = SemVer::parse_lenient(s, allow_wildcards) {
+            return Ok((Self(FabricVersionIn::SemVer(version)), warnings));
+        }
+        Ok((Self::parse(s, allow_wildcards)?, vec![]))
+    }
+
+    /// The version without anything that does not affect ordering, see [`SemVer::canonical`].
+    pub fn canonical(&self) -> Self {
+        match &self.0 {
+            FabricVersionIn::SemVer(version) => Self(FabricVersionIn::SemVer(version.canonical())),
+            FabricVersionIn::String(_) => self.clone(),
+        }
+    }
+
+    pub fn is_prerelease(&self) -> bool {
+        self.as_semver().is_some_and(SemVer::is_prerelease)
+    }
+
+    pub fn as_semver(&self) -> Option<&SemVer> {
+        match &self.0 {
+            FabricVersionIn::SemVer(version) => Some(version),
+            FabricVersionIn::String(_) => None,
+        }
+    }
+
+    /// The version if it is not SemVer, it can then only be matched exactly.
+    pub fn as_string(&self) -> Option<&str> {
+        match &self.0 {
+            FabricVersionIn::SemVer(_) => None,
+            FabricVersionIn::String(s) => Some(s),
+        }
+    }
 }
 
 impl cmp::Ord for FabricVersion {
@@ -281,7 +661,11 @@ impl fmt::Display for FabricVersion {
 
 /// An extended version of SemVer.
 /// See also [`FabricVersion`].
-#[derive(PartialEq, Eq, Clone)]
+///
+/// Equality follows the ordering, so `1` and `1.0.0+build` are equal.
+/// A wildcard version sorts after every version it covers, e.g. `1.0 < 1.5 < 1.x < 2`,
+/// ranges still match `1.x` against every `1.` version.
+#[derive(Clone)]
 pub struct SemVer {
     components: Vec<u64>,
     prerelease: Option<Vec<PrereleaseComponent>>,
@@ -392,14 +776,75 @@ impl SemVer {
         })
     }
 
+    /// Like [`SemVer::parse`], but allows a `v` before the version, e.g. `v1.2.3`.
+    pub fn parse_lenient(s: &str, allow_wildcards: bool) -> Result<(Self, Vec<Located<Warning>>)> {
+        let trimmed = s.trim_start();
+        match lenient::strip_v(trimmed) {
+            Some(rest) => {
+                let offset = s.len() - trimmed.len();
+                let warning = Located::new(Warning::LeadingV, s, offset, &trimmed[..1]);
+                Ok((Self::parse(rest, allow_wildcards)?, vec![warning]))
+            }
+            None => Ok((Self::parse(s, allow_wildcards)?, vec![])),
+        }
+    }
+
+    pub fn components(&self) -> &[u64] {
+        &self.components
+    }
+
+    /// The dot separated identifiers after `-`, which may be empty as in `2-`.
+    pub fn prerelease(&self) -> Option<&[PrereleaseComponent]> {
+        self.prerelease.as_deref()
+    }
+
+    pub fn build(&self) -> Option<&str> {
+        self.build.as_deref()
+    }
+
+    /// Whether the last component is a wildcard, e.g. `1.2.x`.
+    pub fn has_wildcard(&self) -> bool {
+        self.has_wildcard
+    }
+
+    pub fn is_prerelease(&self) -> bool {
+        self.prerelease.is_some()
+    }
+
+    /// The version without trailing zeros and build metadata, e.g. `1.0.0+build` is `1`.
+    pub fn canonical(&self) -> Self {
+        let (components, prerelease, has_wildcard) = self.key();
+        Self {
+            components: components.to_vec(),
+            prerelease: prerelease.clone(),
+            build: None,
+            has_wildcard,
+        }
+    }
+
+    fn key(&self) -> (&[u64], &Option<Vec<PrereleaseComponent>>, bool) {
+        let mut components = &self.components[..];
+        // `1.0.x` and `1.x` are different patterns.
+        if !self.has_wildcard {
+            while let [rest @ .., 0] = components
+                && !rest.is_empty()
+            {
+                components = rest;
+            }
+        }
+        (components, &self.prerelease, self.has_wildcard)
+    }
+
     fn inc_major(&mut self) -> &mut Self {
         self.extend_to(1);
+        self.components.truncate(1);
         self.components[0] += 1;
         self
     }
 
     fn inc_minor(&mut self) -> &mut Self {
         self.extend_to(2);
+        self.components.truncate(2);
         self.components[1] += 1;
         self
     }
@@ -410,14 +855,99 @@ impl SemVer {
         }
     }
 
-    fn prerelease(&mut self) -> &mut Self {
+    fn first_prerelease(&mut self) -> &mut Self {
         self.prerelease = Some(vec![]);
         self
     }
 }
 
-#[derive(PartialEq, Eq, Clone, Debug)]
-enum PrereleaseComponent {
+impl SemVer {
+    pub fn bump_major(&self) -> Self {
+        self.bump(0)
+    }
+
+    pub fn bump_minor(&self) -> Self {
+        self.bump(1)
+    }
+
+    pub fn bump_patch(&self) -> Self {
+        self.bump(2)
+    }
+
+    /// Increment the component at `index` and reset the ones after it to zero,
+    /// dropping the prerelease, build metadata and wildcard, e.g. `bump(1)` of `1.2.3-beta` is `1.3.0`.
+    pub fn bump(&self, index: usize) -> Self {
+        let mut version = Self {
+            components: self.components.clone(),
+            prerelease: None,
+            build: None,
+            has_wildcard: false,
+        };
+        version.extend_to(index + 1);
+        version.components[index] += 1;
+        version.components[index + 1..].fill(0);
+        version
+    }
+
+    /// Replace the prerelease with dot separated identifiers, e.g. `alpha.1`.
+    pub fn with_prerelease(&self, prerelease: &str) -> Self {
+        Self {
+            prerelease: Some(
+                prerelease
+                    .split('.')
+                    .filter(|x| !x.is_empty())
+                    .map(PrereleaseComponent::parse)
+                    .collect(),
+            ),
+            ..self.clone()
+        }
+    }
+
+    pub fn without_prerelease(&self) -> Self {
+        Self {
+            prerelease: None,
+            ..self.clone()
+        }
+    }
+
+    /// Increment the last prerelease identifier if it is a number, e.g. `alpha.1` to `alpha.2`,
+    /// otherwise append `.1`. Like npm, a release starts the prerelease of the next patch at `0`.
+    pub fn bump_prerelease(&self) -> Self {
+        let Some(prerelease) = &self.prerelease else {
+            let index = self.components.len().max(3) - 1;
+            return self.bump(index).with_prerelease("0");
+        };
+
+        let mut prerelease = prerelease.clone();
+        match prerelease.last_mut() {
+            Some(PrereleaseComponent::Number(x)) => *x += 1,
+            _ => prerelease.push(PrereleaseComponent::Number(1)),
+        }
+        Self {
+            prerelease: Some(prerelease),
+            build: None,
+            ..self.clone()
+        }
+    }
+
+    /// Replace the build metadata, e.g. `mc1.20.1`.
+    pub fn with_build(&self, build: &str) -> Self {
+        Self {
+            build: Some(build.to_string()),
+            ..self.clone()
+        }
+    }
+
+    pub fn without_build(&self) -> Self {
+        Self {
+            build: None,
+            ..self.clone()
+        }
+    }
+}
+
+#[derive(PartialEq, Eq, Hash, Clone, Debug)]
+pub enum PrereleaseComponent {
     Number(u64),
     String(String),
 }
@@ -469,19 +999,42 @@ enum Component {
     Wildcard,
 }
 
-impl cmp::Ord for SemVer {
-    fn cmp(&self, other: &Self) -> cmp::Ordering {
+impl PartialEq for SemVer {
+    fn eq(&self, other: &Self) -> bool {
+        self.key() == other.key()
+    }
+}
+
+impl Eq for SemVer {}
+
+impl Hash for SemVer {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.key().hash(state);
+    }
+}
+
+impl SemVer {
+    /// Compare like Fabric Loader does when matching a range,
+    /// a wildcard is `Equal` to every component it covers, e.g. `1.x` to both `1.0` and `1.5`.
+    fn cmp_matching(&self, other: &Self) -> cmp::Ordering {
+        self.cmp_with(other, true)
+    }
+
+    fn cmp_with(&self, other: &Self, covering: bool) -> cmp::Ordering {
         let len = self.components.len().max(other.components.len()) + 1;
 
         for i in 0..len {
             match (self.get(i), other.get(i)) {
-                (Component::Wildcard, _) | (_, Component::Wildcard) => return cmp::Ordering::Equal,
                 (Component::Component(this), Component::Component(other)) => {
                     match this.cmp(&other) {
                         cmp::Ordering::Equal => {}
                         ord => return ord,
                     }
                 }
+                _ if covering => return cmp::Ordering::Equal,
+                (Component::Wildcard, Component::Wildcard) => {}
+                (Component::Wildcard, _) => return cmp::Ordering::Greater,
+                (_, Component::Wildcard) => return cmp::Ordering::Less,
             }
         }
 
@@ -514,6 +1067,12 @@ impl cmp::Ord for SemVer {
     }
 }
 
+impl cmp::Ord for SemVer {
+    fn cmp(&self, other: &Self) -> cmp::Ordering {
+        self.cmp_with(other, false)
+    }
+}
+
 impl cmp::PartialOrd for SemVer {
     fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
         Some(self.cmp(other))
@@ -536,10 +1095,14 @@ pub enum Error {
     ParseInt(#[from] ParseIntError),
     #[error("empty")]
     Empty,
+    #[error(transparent)]
+    Located(Box<Located<Error>>),
 }
 
 #[cfg(test)]
 mod test {
+    use std::collections::{BTreeSet, HashSet};
+
     use super::*;
 
     #[test]
@@ -565,6 +1128,122 @@ mod test {
         assert!(range.matches(&FabricVersion::parse("abc", true).unwrap()));
     }
 
+    #[test]
+    fn satisfiable_version_range() {
+        for s in [
+            ">=1 <2",
+            "<=1 >=1",
+            "^1.2 >1.9",
+            "1.x",
+            "1.x <1.5",
+            "=abc",
+            "*",
+        ] {
+            assert!(
+                FabricVersionRange::parse_single(s)
+                    .unwrap()
+                    .is_satisfiable(),
+                "{s}"
+            );
+        }
+        for s in [
+            ">=2 <1",
+            "<1 >=1",
+            "~1.2 <1.2",
+            "=abc =def",
+            "=abc >1",
+            "1.x >=2",
+            "1.2.x <1.2-",
+        ] {
+            assert!(
+                !FabricVersionRange::parse_single(s)
+                    .unwrap()
+                    .is_satisfiable(),
+                "{s}"
+            );
+        }
+        assert!(!FabricVersionRange::empty().is_satisfiable());
+    }
+
+    #[test]
+    fn caret_and_tilde_ranges() {
+        let range = FabricVersionRange::parse_single("^1.2").unwrap();
+        assert!(range.matches(&FabricVersion::parse("1.2", true).unwrap()));
+        assert!(range.matches(&FabricVersion::parse("1.9.5", true).unwrap()));
+        assert!(!range.matches(&FabricVersion::parse("2.0", true).unwrap()));
+        assert!(!range.matches(&FabricVersion::parse("2.1", true).unwrap()));
+
+        let range = FabricVersionRange::parse_single("~1.2.3").unwrap();
+        assert!(range.matches(&FabricVersion::parse("1.2.9", true).unwrap()));
+        assert!(!range.matches(&FabricVersion::parse("1.3.0", true).unwrap()));
+        assert!(!range.matches(&FabricVersion::parse("1.3.1", true).unwrap()));
+
+        for s in ["^1.2 >=2", "~1.2.3 >=1.3"] {
+            assert!(
+                !FabricVersionRange::parse_single(s)
+                    .unwrap()
+                    .is_satisfiable(),
+                "{s}"
+            );
+        }
+    }
+
+    #[test]
+    fn predicate_error_location() {
+        let Error::Located(e) = FabricVersionPredicate::parse(" >=1.0 <1.x.2").unwrap_err() else {
+            panic!("no location");
+        };
+        assert!(matches!(e.error, Error::InterjacentWildcards));
+        assert_eq!((e.offset, e.token.as_str()), (7, "<1.x.2"));
+        assert_eq!(e.underline(), " >=1.0 <1.x.2\n       ^^^^^^");
+    }
+
+    #[test]
+    fn parse_lenient() {
+        let (predicate, warnings) = FabricVersionPredicate::parse_lenient(">=1.0  <v2 *").unwrap();
+        assert_eq!(predicate.to_string(), ">=1.0 <2");
+        let warnings: Vec<_> = warnings.iter().map(|w| (w.error, w.offset)).collect();
+        assert_eq!(
+            warnings,
+            vec![
+                (Warning::RepeatedSpace, 6),
+                (Warning::LeadingV, 8),
+                (Warning::IgnoredWildcard, 11)
+            ]
+        );
+        let Err(Error::Located(e)) = FabricVersionPredicate::parse(">=1.0  <2") else {
+            panic!("an empty term is not valid in strict parsing");
+        };
+        assert!(matches!(e.error, Error::Empty));
+        assert_eq!((e.offset, e.token.as_str()), (6, " "));
+        assert!(FabricVersionPredicate::parse("<v2").is_err());
+
+        let (predicate, _) = FabricVersionPredicate::parse_lenient("*  *").unwrap();
+        assert_eq!(predicate.terms(), None);
+
+        for s in ["", "  "] {
+            let (predicate, warnings) = FabricVersionPredicate::parse_lenient(s).unwrap();
+            assert_eq!(predicate.terms(), None);
+            let warnings: Vec<_> = warnings.iter().map(|w| (w.error, w.offset)).collect();
+            assert_eq!(warnings, vec![(Warning::EmptyPredicate, 0)]);
+            assert!(FabricVersionPredicate::parse(s).is_err());
+        }
+
+        let (version, warnings) = FabricVersion::parse_lenient("v1.2.3", false).unwrap();
+        assert_eq!(
+            version.as_semver(),
+            Some(&SemVer::parse("1.2.3", false).unwrap())
+        );
+        assert_eq!(warnings[0].underline(), "v1.2.3\n^");
+        assert!(
+            FabricVersion::parse("v1.2.3", false)
+                .unwrap()
+                .as_semver()
+                .is_none()
+        );
+        assert!(SemVer::parse_lenient("1.2.3", false).unwrap().1.is_empty());
+    }
+
     #[test]
     fn ord_semver() {
         let mut vers = [
@@ -589,10 +1268,173 @@ mod test {
         assert_eq!(vers, expected);
     }
 
+    #[test]
+    fn bump_semver() {
+        let bump =
+            |s: &str, f: fn(&SemVer) -> SemVer| f(&SemVer::parse(s, false).unwrap()).to_string();
+        assert_eq!(bump("1.2.3-beta+build", SemVer::bump_major), "2.0.0");
+        assert_eq!(bump("1.2.3", SemVer::bump_minor), "1.3.0");
+        assert_eq!(bump("1.2.3", SemVer::bump_patch), "1.2.4");
+        assert_eq!(bump("1", SemVer::bump_patch), "1.0.1");
+        assert_eq!(bump("1.2.3.4", SemVer::bump_minor), "1.3.0.0");
+
+        assert_eq!(
+            bump("1.0.0-alpha.1", SemVer::bump_prerelease),
+            "1.0.0-alpha.2"
+        );
+        assert_eq!(
+            bump("1.0.0-alpha", SemVer::bump_prerelease),
+            "1.0.0-alpha.1"
+        );
+        assert_eq!(bump("1.0.0", SemVer::bump_prerelease), "1.0.1-0");
+        assert_eq!(bump("1.0.0-rc.1", SemVer::without_prerelease), "1.0.0");
+
+        let version = SemVer::parse("1.4.2", false).unwrap();
+        assert_eq!(
+            version.with_prerelease("beta.3").to_string(),
+            "1.4.2-beta.3"
+        );
+        assert_eq!(version.with_build("mc1.20.1").to_string(), "1.4.2+mc1.20.1");
+        assert!(version.bump_prerelease() > version);
+        assert!(
+            version.with_prerelease("alpha").bump_prerelease() > version.with_prerelease("alpha")
+        );
+        assert_eq!(
+            SemVer::parse("1.x", true).unwrap().bump_minor().to_string(),
+            "1.1"
+        );
+    }
+
+    #[test]
+    fn canonical_semver() {
+        let parse = |s| SemVer::parse(s, true).unwrap();
+
+        assert_eq!(parse("1.0.0+build").canonical().to_string(), "1");
+        assert_eq!(parse("0.0").canonical().to_string(), "0");
+        assert_eq!(parse("1.0.0-rc.1").canonical().to_string(), "1-rc.1");
+        assert_eq!(parse("1.0.x").canonical().to_string(), "1.0.*");
+
+        let versions: HashSet<SemVer> = ["1", "1.0", "1.0.0", "1.0.0+build"]
+            .into_iter()
+            .map(parse)
+            .collect();
+        assert_eq!(versions.len(), 1);
+        assert_ne!(parse("1.0-rc"), parse("1.0"));
+        assert_ne!(parse("1.0.x"), parse("1.x"));
+
+        let versions: BTreeSet<SemVer> = ["1.x", "1.5", "2", "1.0", "1.0.x", "1.x.x"]
+            .into_iter()
+            .map(parse)
+            .collect();
+        let versions: Vec<String> = versions.iter().map(ToString::to_string).collect();
+        assert_eq!(versions, ["1.0", "1.0.*", "1.5", "1.*", "2"]);
+        assert!(parse("1.0") < parse("1.x") && parse("1.5") < parse("1.x"));
+        assert_eq!(parse("1.x").cmp(&parse("1.x.x")), cmp::Ordering::Equal);
+        assert_eq!(parse("1.x"), parse("1.x.x"));
+
+        let versions: HashSet<FabricVersion> = ["1.0", "1", "1.0.0", "abc", "abc"]
+            .into_iter()
+            .map(|s| FabricVersion::parse(s, false).unwrap())
+            .collect();
+        assert_eq!(versions.len(), 2);
+    }
+
+    #[test]
+    fn satisfying_versions() {
+        let versions: Vec<FabricVersion> = [
+            "1.0.0",
+            "1.1.0",
+            "1.2.0-beta.1",
+            "1.2.0",
+            "2.0.0-alpha",
+            "2.0.0",
+            "abc",
+        ]
+        .into_iter()
+        .map(|s| FabricVersion::parse(s, false).unwrap())
+        .collect();
+        let range = |s| FabricVersionRange::parse_single(s).unwrap();
+        let to_string = |v: Option<&FabricVersion>| v.map(|v| v.to_string());
+
+        let stable = range(">=1.0 <2");
+        assert_eq!(
+            to_string(stable.max_satisfying(&versions, false)).as_deref(),
+            Some("1.2.0")
+        );
+        assert_eq!(
+            to_string(stable.max_satisfying(&versions, true)).as_deref(),
+            Some("2.0.0-alpha")
+        );
+        assert_eq!(
+            to_string(stable.min_satisfying(&versions, false)).as_deref(),
+            Some("1.0.0")
+        );
+
+        // Only prereleases of the version mentioned in the range are opted in.
+        let filtered: Vec<String> = range(">=1.2.0-beta.1")
+            .filter(&versions, false)
+            .map(|v| v.to_string())
+            .collect();
+        assert_eq!(filtered, vec!["1.2.0-beta.1", "1.2.0", "2.0.0"]);
+
+        assert_eq!(range(">=3").max_satisfying(&versions, true), None);
+        assert_eq!(
+            to_string(range("=abc").max_satisfying(&versions, false)).as_deref(),
+            Some("abc")
+        );
+    }
+
+    #[test]
+    fn inspect_range() {
+        let range = FabricVersionRange::parse_many([">=1.2 <2", "^3.1", "*"].iter()).unwrap();
+        let predicates = range.predicates();
+        assert_eq!(predicates[2].terms(), None);
+
+        let terms = predicates[0].terms().unwrap();
+        assert!(matches!(terms[0], FabricVersionTerm::GreaterOrEqual(_)));
+        assert_eq!(terms[1].semver().unwrap().components(), &[2]);
+
+        let bound =
+            |bound: Option<(SemVer, bool)>| bound.map(|(v, inclusive)| (v.to_string(), inclusive));
+        assert_eq!(
+            bound(predicates[1].lower_bound()),
+            Some(("3.1".into(), true))
+        );
+        assert_eq!(
+            bound(predicates[1].upper_bound()),
+            Some(("4-".into(), false))
+        );
+        assert_eq!(bound(range.lower_bound()), None);
+
+        let range =
+            FabricVersionRange::parse_many([">=1.2 <2", "^3.1", ">=1.2 <=1.2", ">=5 <1"].iter())
+                .unwrap();
+        assert_eq!(bound(range.lower_bound()), Some(("1.2".into(), true)));
+        assert_eq!(bound(range.upper_bound()), Some(("4-".into(), false)));
+
+        let version = SemVer::parse("1.2.3-beta.4+build", false).unwrap();
+        assert_eq!(version.components(), &[1, 2, 3]);
+        assert_eq!(
+            version.prerelease(),
+            Some(
+                &[
+                    PrereleaseComponent::String("beta".into()),
+                    PrereleaseComponent::Number(4)
+                ][..]
+            )
+        );
+        assert_eq!(version.build(), Some("build"));
+        assert!(!version.has_wildcard());
+        assert_eq!(
+            FabricVersion::parse("abc", false).unwrap().as_string(),
+            Some("abc")
+        );
+    }
+
     #[test]
     fn parse_term() {
         assert_eq!(
-            FabricVersionTerm::parse(">=1").unwrap(),
+            FabricVersionTerm::parse(">=1", false).unwrap().0,
             FabricVersionTerm::GreaterOrEqual(SemVer {
                 components: vec![1],
                 prerelease: None,
@@ -601,7 +1443,7 @@ mod test {
             })
         );
         assert_eq!(
-            FabricVersionTerm::parse("<1").unwrap(),
+            FabricVersionTerm::parse("<1", false).unwrap().0,
             FabricVersionTerm::Less(SemVer {
                 components: vec![1],
                 prerelease: None,
@@ -610,7 +1452,7 @@ mod test {
             })
         );
         assert_eq!(
-            FabricVersionTerm::parse("=1").unwrap(),
+            FabricVersionTerm::parse("=1", false).unwrap().0,
             FabricVersionTerm::Equal(FabricVersion(FabricVersionIn::SemVer(SemVer {
                 components: vec![1],
                 prerelease: None,
@@ -619,7 +1461,7 @@ mod test {
             })))
         );
         assert_eq!(
-            FabricVersionTerm::parse("=abc").unwrap(),
+            FabricVersionTerm::parse("=abc", false).unwrap().0,
             FabricVersionTerm::Equal(FabricVersion(FabricVersionIn::String("abc".into())))
         );
     }
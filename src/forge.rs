@@ -1,28 +1,68 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, fmt};
 
-use serde::{Deserialize, de};
+use serde::{Deserialize, Serialize, de};
 
-use crate::forge::version::{ForgeVersion, ForgeVersionRange};
+use crate::{
+    forge::version::{ForgeVersion, ForgeVersionRange},
+    lenient::ManifestWarning,
+    metadata::{self, ModIdError},
+};
 
+pub mod mcmod_info;
+pub mod update;
 pub mod version;
 
-pub type ModId = String;
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Hash, Clone)]
+#[serde(try_from = "String")]
+pub struct ModId(pub String);
+
+impl ModId {
+    /// Check that an id matches `^[a-z][a-z0-9_]{1,63}$`.
+    pub fn validate(id: &str) -> Result<(), ModIdError> {
+        metadata::validate_mod_id(id, &['_'])
+    }
+}
+
+impl TryFrom<String> for ModId {
+    type Error = Error;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::validate(&value)?;
+        Ok(ModId(value))
+    }
+}
+
+impl fmt::Display for ModId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
 
 /// A `META-INF/mods.toml` file.
 /// Contains metadata about a forge mod.
-#[derive(Debug)]
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
 pub struct ForgeManifest {
     pub mod_loader: String,
     pub loader_version: ForgeVersionRange,
     pub license: String,
+    #[serde(rename = "issueTrackerURL")]
     pub issue_tracker_url: Option<String>,
     pub show_as_resource_pack: bool,
+    pub client_side_only: bool,
+    #[serde(rename = "displayURL")]
+    pub display_url: Option<String>,
+    pub logo_file: Option<String>,
+    pub services: Vec<String>,
     pub properties: HashMap<String, String>,
     pub mods: Vec<Mod>,
-    pub dependencies: HashMap<ModId, Vec<Dependency>>,
+    pub dependencies: HashMap<String, Vec<Dependency>>,
+    pub access_transformers: Vec<AccessTransformer>,
+    pub mixins: Vec<MixinConfig>,
 }
 
-#[derive(Debug)]
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
 pub struct Mod {
     pub mod_id: ModId,
     pub namespace: Option<String>,
@@ -31,15 +71,19 @@ pub struct Mod {
     pub description: Option<String>,
     pub logo_file: Option<String>,
     pub logo_blur: bool,
+    #[serde(rename = "updateJSONURL")]
     pub update_json_url: Option<String>,
-    //pub modproperties: HashMap<>
     pub credits: Option<String>,
     pub authors: Option<String>,
-    pub display_test: Option<String>,
+    pub display_test: Option<DisplayTest>,
+    /// The mod's `[modproperties.<modid>]` table.
+    pub mod_properties: toml::Table,
+    /// The mod's `[features.<modid>]` table, e.g. `javaVersion` mapped to `[17,)`.
+    pub features: HashMap<String, ForgeVersionRange>,
 }
 
 /// `META-INF/mods.toml` before string substitution.
-#[derive(Deserialize, PartialEq, Debug)]
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct UnsubstitutedForgeManifest {
     pub mod_loader: String,
@@ -49,13 +93,47 @@ pub struct UnsubstitutedForgeManifest {
     pub issue_tracker_url: Option<String>,
     #[serde(default)]
     pub show_as_resource_pack: bool,
+    /// Only load the jar on the client, NeoForge skips it on a dedicated server.
+    #[serde(default)]
+    pub client_side_only: bool,
+    #[serde(rename = "displayURL")]
+    pub display_url: Option<String>,
+    pub logo_file: Option<String>,
+    /// Service interfaces the jar uses through `ServiceLoader`.
+    #[serde(default)]
+    pub services: Vec<String>,
     #[serde(default)]
     pub properties: HashMap<String, String>,
     pub mods: Vec<UnsubstitutedMod>,
-    pub dependencies: HashMap<ModId, Vec<Dependency>>,
+    #[serde(default)]
+    pub dependencies: HashMap<String, Vec<Dependency>>,
+    /// Mod id mapped to the mod's `[modproperties.<modid>]` table.
+    #[serde(default, rename = "modproperties")]
+    pub mod_properties: HashMap<String, toml::Table>,
+    /// Mod id mapped to the versions of the environment the mod needs.
+    #[serde(default)]
+    pub features: HashMap<String, HashMap<String, ForgeVersionRange>>,
+    #[serde(default)]
+    pub access_transformers: Vec<AccessTransformer>,
+    #[serde(default)]
+    pub mixins: Vec<MixinConfig>,
+}
+
+/// An `[[accessTransformers]]` entry.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone)]
+pub struct AccessTransformer {
+    /// Path of the transformer in the jar, e.g. `META-INF/accesstransformer.cfg`.
+    pub file: String,
+}
+
+/// A `[[mixins]]` entry.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone)]
+pub struct MixinConfig {
+    /// Path of the mixin config in the jar, e.g. `examplemod.mixins.json`.
+    pub config: String,
 }
 
-#[derive(Deserialize, PartialEq, Eq, Debug)]
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct UnsubstitutedMod {
     pub mod_id: ModId,
@@ -69,10 +147,130 @@ pub struct UnsubstitutedMod {
     pub logo_blur: bool,
     #[serde(rename = "updateJSONURL")]
     pub update_json_url: Option<String>,
-    //pub modproperties: HashMap<>
     pub credits: Option<String>,
     pub authors: Option<String>,
-    pub display_test: Option<String>,
+    pub display_test: Option<DisplayTest>,
+}
+
+impl UnsubstitutedForgeManifest {
+    /// Parse a `mods.toml`, normalizing ranges that Forge accepts but strict parsing rejects.
+    pub fn parse_lenient(s: &str) -> Result<(Self, Vec<ManifestWarning>), toml::de::Error> {
+        let mut table: toml::Table = toml::from_str(s)?;
+        let mut warnings = vec![];
+
+        let mut normalize = |field: String, value: &mut toml::Value| {
+            if let Some(s) = value.as_str()
+                && let Ok((range, w)) = ForgeVersionRange::parse_lenient(s)
+                && !w.is_empty()
+            {
+                *value = range.to_string().into();
+                warnings.extend(w.into_iter().map(|warning| ManifestWarning {
+                    field: field.clone(),
+                    warning,
+                }));
+            }
+        };
+
+        if let Some(value) = table.get_mut("loaderVersion") {
+            normalize("loaderVersion".into(), value);
+        }
+        if let Some(dependencies) = table
+            .get_mut("dependencies")
+            .and_then(toml::Value::as_table_mut)
+        {
+            for (id, deps) in dependencies {
+                for (i, dep) in deps.as_array_mut().into_iter().flatten().enumerate() {
+                    if let Some(value) = dep.get_mut("versionRange") {
+                        normalize(format!("dependencies.{id}[{i}].versionRange"), value);
+                    }
+                }
+            }
+        }
+        if let Some(features) = table
+            .get_mut("features")
+            .and_then(toml::Value::as_table_mut)
+        {
+            for (id, features) in features {
+                for (name, value) in features.as_table_mut().into_iter().flatten() {
+                    normalize(format!("features.{id}.{name}"), value);
+                }
+            }
+        }
+
+        Ok((table.try_into()?, warnings))
+    }
+
+    /// Replace `${file.jarVersion}` with the `Implementation-Version` of the jar manifest.
+    pub fn substitute(
+        self,
+        implementation_version: Option<&ForgeVersion>,
+    ) -> Result<ForgeManifest, Error> {
+        let mut mod_properties = self.mod_properties;
+        let mut features = self.features;
+        let mods = self
+            .mods
+            .into_iter()
+            .map(|m| {
+                let mut m = m.substitute(implementation_version)?;
+                m.mod_properties = mod_properties.remove(&m.mod_id.0).unwrap_or_default();
+                m.features = features.remove(&m.mod_id.0).unwrap_or_default();
+                Ok(m)
+            })
+            .collect::<Result<_, Error>>()?;
+
+        Ok(ForgeManifest {
+            mod_loader: self.mod_loader,
+            loader_version: self.loader_version,
+            license: self.license,
+            issue_tracker_url: self.issue_tracker_url,
+            show_as_resource_pack: self.show_as_resource_pack,
+            client_side_only: self.client_side_only,
+            display_url: self.display_url,
+            logo_file: self.logo_file,
+            services: self.services,
+            properties: self.properties,
+            mods,
+            dependencies: self.dependencies,
+            access_transformers: self.access_transformers,
+            mixins: self.mixins,
+        })
+    }
+}
+
+impl UnsubstitutedMod {
+    pub fn substitute(self, implementation_version: Option<&ForgeVersion>) -> Result<Mod, Error> {
+        let version = match self.version {
+            UnsubstitutedForgeVersion::ForgeVersion(version) => version,
+            UnsubstitutedForgeVersion::ImplementationVersion => implementation_version
+                .cloned()
+                .ok_or(Error::MissingImplementationVersion)?,
+        };
+
+        Ok(Mod {
+            mod_id: self.mod_id,
+            namespace: self.namespace,
+            version,
+            display_name: self.display_name,
+            description: self.description,
+            logo_file: self.logo_file,
+            logo_blur: self.logo_blur,
+            update_json_url: self.update_json_url,
+            credits: self.credits,
+            authors: self.authors,
+            display_test: self.display_test,
+            mod_properties: toml::Table::new(),
+            features: HashMap::new(),
+        })
+    }
+}
+
+impl Mod {
+    /// Whether the Java version is in the mod's `javaVersion` feature, true when there is none.
+    pub fn supports_java(&self, java: &ForgeVersion) -> bool {
+        self.features
+            .get("javaVersion")
+            .is_none_or(|range| range.matches(java))
+    }
 }
 
 const fn truthy() -> bool {
@@ -91,11 +289,16 @@ pub enum UnsubstitutedForgeVersion {
     ImplementationVersion,
 }
 
-#[derive(Deserialize, PartialEq, Eq, Debug, Clone)]
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct Dependency {
-    pub mod_id: ModId,
+    pub mod_id: String,
+    /// Forge rejects a dependency without `mandatory` or `type`, NeoForge treats it as required.
+    #[serde(default = "truthy")]
     pub mandatory: bool,
+    /// NeoForge replaces `mandatory` with `type`.
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub dependency_type: Option<DependencyType>,
     pub version_range: ForgeVersionRange,
     #[serde(default)]
     pub ordering: Ordering,
@@ -103,7 +306,27 @@ pub struct Dependency {
     pub side: Side,
 }
 
-#[derive(Deserialize, PartialEq, Eq, Debug, Clone, Default)]
+impl Dependency {
+    pub fn is_mandatory(&self) -> bool {
+        match self.dependency_type {
+            Some(dependency_type) => dependency_type == DependencyType::Required,
+            None => self.mandatory,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum DependencyType {
+    Required,
+    Optional,
+    /// The game will not load if the dependency is present.
+    Incompatible,
+    /// A warning is shown if the dependency is present.
+    Discouraged,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone, Default)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum Ordering {
     #[default]
@@ -112,7 +335,33 @@ pub enum Ordering {
     After,
 }
 
-#[derive(Deserialize, PartialEq, Eq, Debug, Clone, Default)]
+/// How the server list compares the mod's versions on the client and the server.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone, Copy, Default)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum DisplayTest {
+    /// The versions must match.
+    #[default]
+    MatchVersion,
+    /// Only the server needs the mod.
+    IgnoreServerVersion,
+    /// Only the client needs the mod.
+    IgnoreAllVersion,
+    /// The mod registers its own test in code.
+    None,
+}
+
+impl DisplayTest {
+    /// The side the mod is needed on.
+    pub fn side(self) -> metadata::Side {
+        match self {
+            DisplayTest::IgnoreServerVersion => metadata::Side::Server,
+            DisplayTest::IgnoreAllVersion => metadata::Side::Client,
+            DisplayTest::MatchVersion | DisplayTest::None => metadata::Side::Both,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone, Copy, Default)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum Side {
     #[default]
@@ -121,6 +370,22 @@ pub enum Side {
     Server,
 }
 
+impl Side {
+    pub fn applies_to(self, side: metadata::Side) -> bool {
+        metadata::Side::from(self).applies_to(side)
+    }
+}
+
+impl From<Side> for metadata::Side {
+    fn from(value: Side) -> Self {
+        match value {
+            Side::Both => metadata::Side::Both,
+            Side::Client => metadata::Side::Client,
+            Side::Server => metadata::Side::Server,
+        }
+    }
+}
+
 impl<'de> Deserialize<'de> for UnsubstitutedForgeVersion {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -153,6 +418,38 @@ impl<'de> Deserialize<'de> for UnsubstitutedForgeVersion {
     }
 }
 
+impl Serialize for UnsubstitutedForgeVersion {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        match self {
+            UnsubstitutedForgeVersion::ForgeVersion(version) => version.serialize(serializer),
+            UnsubstitutedForgeVersion::ImplementationVersion => {
+                serializer.serialize_str("${file.jarVersion}")
+            }
+        }
+    }
+}
+
+impl Serialize for ForgeVersion {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+impl Serialize for ForgeVersionRange {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
 impl<'de> Deserialize<'de> for ForgeVersionRange {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -179,6 +476,14 @@ impl<'de> Deserialize<'de> for ForgeVersionRange {
     }
 }
 
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("version is ${{file.jarVersion}} but the jar has no Implementation-Version")]
+    MissingImplementationVersion,
+    #[error(transparent)]
+    ModId(#[from] ModIdError),
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -221,7 +526,7 @@ mod test {
             UnsubstitutedForgeManifest {
                 mods: vec![
                     UnsubstitutedMod {
-                        mod_id: "examplemod".into(),
+                        mod_id: ModId("examplemod".into()),
                         version: UnsubstitutedForgeVersion::ForgeVersion(
                             ForgeVersion::parse("1.0.0.0").unwrap()
                         ),
@@ -236,7 +541,7 @@ mod test {
                         display_test: None
                     },
                     UnsubstitutedMod {
-                        mod_id: "othermod".into(),
+                        mod_id: ModId("othermod".into()),
                         version: UnsubstitutedForgeVersion::ImplementationVersion,
                         namespace: None,
                         display_name: Some("Other Mod".into()),
@@ -255,6 +560,7 @@ mod test {
                         Dependency {
                             mod_id: "forge".into(),
                             mandatory: true,
+                            dependency_type: None,
                             version_range: ForgeVersionRange::parse("[41,)").unwrap(),
                             ordering: Ordering::None,
                             side: Side::Both,
@@ -262,6 +568,7 @@ mod test {
                         Dependency {
                             mod_id: "minecraft".into(),
                             mandatory: true,
+                            dependency_type: None,
                             version_range: ForgeVersionRange::parse("[1.19,1.20)").unwrap(),
                             ordering: Ordering::Before,
                             side: Side::Server,
@@ -273,8 +580,200 @@ mod test {
                 license: "All rights reserved".into(),
                 issue_tracker_url: Some("github.com/MinecraftForge/MinecraftForge/issues".into()),
                 show_as_resource_pack: false,
+                client_side_only: false,
+                display_url: None,
+                logo_file: None,
+                services: vec![],
                 properties: HashMap::new(),
+                mod_properties: HashMap::new(),
+                features: HashMap::new(),
+                access_transformers: vec![],
+                mixins: vec![],
             }
         )
     }
+
+    #[test]
+    fn parse_mods_toml_lenient() {
+        let s = r#"
+        modLoader="javafml"
+        loaderVersion="[41,42)[43,)"
+        license="MIT"
+
+        [[mods]]
+        modId="examplemod"
+
+        [[dependencies.examplemod]]
+            modId="minecraft"
+            mandatory=true
+            versionRange="[1.19,1.20)"
+
+        [[dependencies.examplemod]]
+            modId="forge"
+            mandatory=true
+            versionRange="[41,42) (43,)"
+        "#;
+        assert!(toml::from_str::<UnsubstitutedForgeManifest>(s).is_err());
+
+        let (manifest, warnings) = UnsubstitutedForgeManifest::parse_lenient(s).unwrap();
+        assert_eq!(manifest.loader_version.to_string(), "[41,42),[43,)");
+        let fields: Vec<_> = warnings.iter().map(|w| w.field.as_str()).collect();
+        assert_eq!(
+            fields,
+            vec!["loaderVersion", "dependencies.examplemod[1].versionRange"]
+        );
+        assert_eq!(warnings[1].warning.underline(), "[41,42) (43,)\n        ^");
+    }
+
+    #[test]
+    fn parse_display_test_and_mod_properties() {
+        let s = r#"
+        modLoader="javafml"
+        loaderVersion="[41,)"
+        license="MIT"
+
+        [[mods]]
+        modId="examplemod"
+        displayTest="IGNORE_ALL_VERSION"
+
+        [[mods]]
+        modId="othermod"
+
+        [modproperties.examplemod]
+        configScreen=true
+        catalogue = { icon = "icon.png" }"#;
+
+        let manifest: UnsubstitutedForgeManifest = toml::from_str(s).unwrap();
+        assert_eq!(
+            manifest.mods[0].display_test,
+            Some(DisplayTest::IgnoreAllVersion)
+        );
+        assert_eq!(manifest.mods[1].display_test, None);
+        assert_eq!(
+            DisplayTest::IgnoreServerVersion.side(),
+            metadata::Side::Server
+        );
+        assert!(
+            toml::from_str::<UnsubstitutedForgeManifest>(&s.replace("IGNORE_ALL", "IGNORE_NO"))
+                .is_err()
+        );
+
+        let manifest = manifest.substitute(None).unwrap();
+        let properties = &manifest.mods[0].mod_properties;
+        assert_eq!(properties["configScreen"], toml::Value::Boolean(true));
+        assert_eq!(properties["catalogue"]["icon"].as_str(), Some("icon.png"));
+        assert!(manifest.mods[1].mod_properties.is_empty());
+    }
+
+    #[test]
+    fn parse_features_and_mixins() {
+        let s = r#"
+        modLoader="javafml"
+        loaderVersion="[1,)"
+        license="MIT"
+        clientSideOnly=true
+        displayURL="https://example.com"
+        logoFile="logo.png"
+        services=["net.neoforged.neoforgespi.locating.IModFileCandidateLocator"]
+
+        [[mods]]
+        modId="examplemod"
+
+        [[mods]]
+        modId="othermod"
+
+        [features.examplemod]
+        javaVersion="[17,)"
+        openGLVersion="[3.2,)"
+
+        [[accessTransformers]]
+        file="META-INF/accesstransformer.cfg"
+
+        [[mixins]]
+        config="examplemod.mixins.json""#;
+
+        let manifest: UnsubstitutedForgeManifest = toml::from_str(s).unwrap();
+        assert!(manifest.client_side_only);
+        assert_eq!(manifest.display_url.as_deref(), Some("https://example.com"));
+        assert_eq!(manifest.services.len(), 1);
+        assert_eq!(
+            manifest.access_transformers,
+            vec![AccessTransformer {
+                file: "META-INF/accesstransformer.cfg".into()
+            }]
+        );
+        assert_eq!(manifest.mixins[0].config, "examplemod.mixins.json");
+
+        let manifest = manifest.substitute(None).unwrap();
+        let m = &manifest.mods[0];
+        assert_eq!(
+            m.features["openGLVersion"],
+            ForgeVersionRange::parse("[3.2,)").unwrap()
+        );
+        assert!(m.supports_java(&ForgeVersion::parse("21").unwrap()));
+        assert!(!m.supports_java(&ForgeVersion::parse("1.8").unwrap()));
+        assert!(manifest.mods[1].supports_java(&ForgeVersion::parse("8").unwrap()));
+
+        let s = s.replace(r#"javaVersion="[17,)""#, r#"javaVersion="[17,18)[21,)""#);
+        assert!(toml::from_str::<UnsubstitutedForgeManifest>(&s).is_err());
+        let (manifest, warnings) = UnsubstitutedForgeManifest::parse_lenient(&s).unwrap();
+        assert_eq!(
+            manifest.features["examplemod"]["javaVersion"],
+            ForgeVersionRange::parse("[17,18),[21,)").unwrap()
+        );
+        assert_eq!(warnings[0].field, "features.examplemod.javaVersion");
+    }
+
+    #[test]
+    fn dependency_without_mandatory_is_required() {
+        let dep: Dependency = toml::from_str(
+            r#"
+            modId="minecraft"
+            versionRange="[1.20,)""#,
+        )
+        .unwrap();
+        assert!(dep.is_mandatory());
+
+        let dep: Dependency = toml::from_str(
+            r#"
+            modId="jei"
+            type="optional"
+            versionRange="[1.20,)""#,
+        )
+        .unwrap();
+        assert!(!dep.is_mandatory());
+    }
+
+    #[test]
+    fn validate_mod_id() {
+        assert_eq!(ModId::validate("example_mod2"), Ok(()));
+        assert_eq!(
+            ModId::validate("example-mod").unwrap_err().to_string(),
+            "mod id contains `-`, only `a-z`, `0-9`, `_` are allowed"
+        );
+
+        let manifest: UnsubstitutedForgeManifest = toml::from_str(
+            r#"
+            modLoader="javafml"
+            loaderVersion="[47,)"
+            license="MIT"
+
+            [[mods]]
+            modId="examplemod"
+
+            [[dependencies.examplemod]]
+            modId="Create"
+            mandatory=false
+            versionRange="[0.5,)"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(manifest.dependencies["examplemod"][0].mod_id, "Create");
+        assert!(
+            toml::from_str::<UnsubstitutedMod>(r#"modId="ExampleMod""#)
+                .unwrap_err()
+                .to_string()
+                .contains("mod id must start with a lowercase letter, found `E`")
+        );
+    }
 }
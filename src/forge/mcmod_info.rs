@@ -0,0 +1,58 @@
+use serde::{Deserialize, Serialize};
+
+/// An entry in the `mcmod.info` file used by Forge before 1.13.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct McModInfo {
+    #[serde(rename = "modid")]
+    pub mod_id: String,
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub version: Option<String>,
+    #[serde(rename = "mcversion")]
+    pub mc_version: Option<String>,
+    #[serde(default)]
+    pub author_list: Vec<String>,
+    #[serde(default)]
+    pub dependencies: Vec<String>,
+}
+
+/// The file is either a plain list of mods or the version 2 format wrapping it in `modList`.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum McModInfoFile {
+    List(Vec<McModInfo>),
+    #[serde(rename_all = "camelCase")]
+    V2 {
+        mod_list: Vec<McModInfo>,
+    },
+}
+
+pub fn parse(s: &str) -> Result<Vec<McModInfo>, serde_json::Error> {
+    match serde_json::from_str(s)? {
+        McModInfoFile::List(mods) | McModInfoFile::V2 { mod_list: mods } => Ok(mods),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_mcmod_info() {
+        let list = r#"[{
+            "modid": "examplemod",
+            "name": "Example Mod",
+            "version": "1.0",
+            "mcversion": "1.12.2",
+            "authorList": ["Me"]
+        }]"#;
+        let mods = parse(list).unwrap();
+        assert_eq!(mods[0].mod_id, "examplemod");
+        assert_eq!(mods[0].mc_version.as_deref(), Some("1.12.2"));
+        assert_eq!(mods[0].author_list, vec!["Me"]);
+
+        let v2 = r#"{ "modListVersion": 2, "modList": [{ "modid": "examplemod" }] }"#;
+        assert_eq!(parse(v2).unwrap()[0].mod_id, "examplemod");
+    }
+}
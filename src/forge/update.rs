@@ -0,0 +1,157 @@
+use std::{cmp, collections::HashMap};
+
+use serde::{Deserialize, Serialize};
+
+use crate::forge::version::{self, ForgeVersion};
+
+/// The JSON file an `updateJSONURL` points to.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone, Default)]
+pub struct UpdateJson {
+    pub homepage: Option<String>,
+    /// `<mc version>-latest` and `<mc version>-recommended` mapped to a mod version.
+    #[serde(default)]
+    pub promos: HashMap<String, String>,
+    /// Minecraft version mapped to the changelog of every mod version for it.
+    #[serde(flatten)]
+    pub changelogs: HashMap<String, HashMap<String, String>>,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone, Copy)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum UpdateStatus {
+    /// The current version is the recommended version.
+    UpToDate,
+    /// There is a newer recommended or latest version.
+    Outdated,
+    /// The current version is newer than the recommended version.
+    Ahead,
+    /// There is no recommended version and the current version is not older than the latest.
+    Beta,
+    /// There is no recommended version and the latest version is newer.
+    BetaOutdated,
+}
+
+/// The outcome of [`UpdateJson::check`].
+#[derive(Serialize, PartialEq, Eq, Debug, Clone)]
+pub struct UpdateCheck {
+    pub status: UpdateStatus,
+    /// The version to update to.
+    pub target: Option<ForgeVersion>,
+    /// Changelogs of the versions after the current one up to and including the target, oldest first.
+    pub changes: Vec<(ForgeVersion, String)>,
+    pub homepage: Option<String>,
+}
+
+impl UpdateJson {
+    /// Evaluate the update status the same way as Forge's `VersionChecker`.
+    pub fn check(&self, current: &ForgeVersion, mc_version: &str) -> version::Result<UpdateCheck> {
+        let promo = |kind: &str| {
+            self.promos
+                .get(&format!("{mc_version}-{kind}"))
+                .map(|version| ForgeVersion::parse(version))
+                .transpose()
+        };
+        let recommended = promo("recommended")?;
+        let latest = promo("latest")?;
+
+        let (status, target) = match (recommended, latest) {
+            (Some(recommended), latest) => match recommended.cmp(current) {
+                cmp::Ordering::Equal => (UpdateStatus::UpToDate, None),
+                cmp::Ordering::Less => match latest {
+                    Some(latest) if *current < latest => (UpdateStatus::Outdated, Some(latest)),
+                    _ => (UpdateStatus::Ahead, None),
+                },
+                cmp::Ordering::Greater => (UpdateStatus::Outdated, Some(recommended)),
+            },
+            (None, Some(latest)) if *current < latest => (UpdateStatus::BetaOutdated, Some(latest)),
+            (None, Some(latest)) => (UpdateStatus::Beta, Some(latest)),
+            (None, None) => (UpdateStatus::Beta, None),
+        };
+
+        let mut changes: Vec<(ForgeVersion, String)> = self
+            .changelogs
+            .get(mc_version)
+            .into_iter()
+            .flatten()
+            .filter_map(|(version, changelog)| {
+                let version = ForgeVersion::parse(version).ok()?;
+                let in_range =
+                    version > *current && target.as_ref().is_none_or(|target| version <= *target);
+                in_range.then(|| (version, changelog.clone()))
+            })
+            .collect();
+        changes.sort_by(|a, b| a.0.cmp(&b.0));
+
+        Ok(UpdateCheck {
+            status,
+            target,
+            changes,
+            homepage: self.homepage.clone(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn update_status() {
+        let update: UpdateJson = serde_json::from_str(
+            r#"{
+                "homepage": "https://example.com/examplemod",
+                "promos": {
+                    "1.20.1-latest": "1.3.0",
+                    "1.20.1-recommended": "1.2.0",
+                    "1.19.2-latest": "1.1.0-beta"
+                },
+                "1.20.1": {
+                    "1.1.0": "Initial port",
+                    "1.2.0": "Fixes",
+                    "1.3.0": "New features"
+                },
+                "1.19.2": {
+                    "1.0.0": "Initial release",
+                    "1.1.0-beta": "Testing"
+                }
+            }"#,
+        )
+        .unwrap();
+        let check = |current: &str, mc_version: &str| {
+            update
+                .check(&ForgeVersion::parse(current).unwrap(), mc_version)
+                .unwrap()
+        };
+
+        let result = check("1.1.0", "1.20.1");
+        assert_eq!(result.status, UpdateStatus::Outdated);
+        assert_eq!(result.target, Some(ForgeVersion::parse("1.2.0").unwrap()));
+        assert_eq!(
+            result.changes,
+            vec![(ForgeVersion::parse("1.2.0").unwrap(), "Fixes".into())]
+        );
+        assert_eq!(
+            result.homepage.as_deref(),
+            Some("https://example.com/examplemod")
+        );
+
+        assert_eq!(check("1.2.0", "1.20.1").status, UpdateStatus::UpToDate);
+        assert_eq!(check("1.2", "1.20.1").status, UpdateStatus::UpToDate);
+
+        let result = check("1.2.5", "1.20.1");
+        assert_eq!(result.status, UpdateStatus::Outdated);
+        assert_eq!(result.target, Some(ForgeVersion::parse("1.3.0").unwrap()));
+
+        let result = check("1.4.0", "1.20.1");
+        assert_eq!(result.status, UpdateStatus::Ahead);
+        assert!(result.changes.is_empty());
+
+        let result = check("1.0.0", "1.19.2");
+        assert_eq!(result.status, UpdateStatus::BetaOutdated);
+        assert_eq!(result.changes.len(), 1);
+
+        assert_eq!(check("1.1.0", "1.19.2").status, UpdateStatus::Beta);
+        assert_eq!(check("1.0.0", "1.18.2").status, UpdateStatus::Beta);
+        assert_eq!(check("1.0.0", "1.18.2").target, None);
+    }
+}
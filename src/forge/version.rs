@@ -1,4 +1,9 @@
-use std::{cmp, fmt, mem};
+use std::{
+    cmp, fmt,
+    hash::{Hash, Hasher},
+};
+
+use crate::{lenient::Warning, located::Located};
 
 /// A Maven version range used by Forge mods.
 ///
@@ -9,16 +14,18 @@ use std::{cmp, fmt, mem};
 /// `1.0`   same as [1.0,) or x >= 1.0
 /// `(,1.0],[1.2,)` x <= 1.0 OR x >= 1.2
 #[derive(PartialEq, Eq, Debug, Clone)]
-pub struct ForgeVersionRange(Vec<ForgeVersionRangeIn>);
+pub struct ForgeVersionRange(pub(crate) Vec<ForgeVersionRangeIn>);
 
+/// A single restriction of a [`ForgeVersionRange`], e.g. `[1.0]` or `(,1.0]`.
 #[derive(PartialEq, Eq, Debug, Clone)]
-enum ForgeVersionRangeIn {
+pub enum ForgeVersionRangeIn {
     Equal(ForgeVersion),
     Range(Bound, Bound),
 }
 
+/// `None` is unbounded.
 #[derive(PartialEq, Eq, Debug, Clone)]
-enum Bound {
+pub enum Bound {
     Inclusive(Option<ForgeVersion>),
     Exclusive(Option<ForgeVersion>),
 }
@@ -35,6 +42,89 @@ impl ForgeVersionRange {
     pub fn only(version: ForgeVersion) -> Self {
         Self(vec![ForgeVersionRangeIn::Equal(version)])
     }
+
+    /// Check if there is any version that can match the range,
+    /// e.g `(2,1)` can never match.
+    pub fn is_satisfiable(&self) -> bool {
+        self.0.iter().any(ForgeVersionRangeIn::is_satisfiable)
+    }
+
+    /// The comma separated restrictions, any of which can match.
+    pub fn restrictions(&self) -> &[ForgeVersionRangeIn] {
+        &self.0
+    }
+
+    /// The lowest version any restriction allows and whether it is inclusive,
+    /// `None` if it is unbounded or nothing matches.
+    pub fn lower_bound(&self) -> Option<(&ForgeVersion, bool)> {
+        self.union_bound(ForgeVersionRangeIn::lower_bound, cmp::Ordering::Less)
+    }
+
+    /// The highest version any restriction allows and whether it is inclusive,
+    /// `None` if it is unbounded or nothing matches.
+    pub fn upper_bound(&self) -> Option<(&ForgeVersion, bool)> {
+        self.union_bound(ForgeVersionRangeIn::upper_bound, cmp::Ordering::Greater)
+    }
+
+    /// Pick the bound that is furthest in the direction of `order` out of every restriction that can match,
+    /// a bound is inclusive if any of the bounds are inclusive.
+    fn union_bound(
+        &self,
+        bound: fn(&ForgeVersionRangeIn) -> Option<(&ForgeVersion, bool)>,
+        order: cmp::Ordering,
+    ) -> Option<(&ForgeVersion, bool)> {
+        let mut result: Option<(&ForgeVersion, bool)> = None;
+
+        for range in self.0.iter().filter(|range| range.is_satisfiable()) {
+            let (version, inclusive) = bound(range)?;
+            result = Some(match result {
+                Some((current, current_inclusive)) => match version.cmp(current) {
+                    cmp::Ordering::Equal => (current, current_inclusive || inclusive),
+                    ord if ord == order => (version, inclusive),
+                    _ => (current, current_inclusive),
+                },
+                None => (version, inclusive),
+            });
+        }
+
+        result
+    }
+}
+
+impl ForgeVersionRange {
+    /// The versions that match the range.
+    /// Versions with a qualifier below release, such as `1.0-SNAPSHOT`, are left out unless
+    /// `include_prereleases` is set or a bound of the same restriction is one of the same release,
+    /// e.g. `[1.0-rc1,)`.
+    pub fn filter<'a>(
+        &self,
+        versions: impl IntoIterator<Item = &'a ForgeVersion>,
+        include_prereleases: bool,
+    ) -> impl Iterator<Item = &'a ForgeVersion> {
+        versions.into_iter().filter(move |version| {
+            self.0.iter().any(|range| {
+                range.matches(version) && (include_prereleases || range.allows_prerelease(version))
+            })
+        })
+    }
+
+    /// The newest version that matches the range, see [`ForgeVersionRange::filter`].
+    pub fn max_satisfying<'a>(
+        &self,
+        versions: impl IntoIterator<Item = &'a ForgeVersion>,
+        include_prereleases: bool,
+    ) -> Option<&'a ForgeVersion> {
+        self.filter(versions, include_prereleases).max()
+    }
+
+    /// The oldest version that matches the range, see [`ForgeVersionRange::filter`].
+    pub fn min_satisfying<'a>(
+        &self,
+        versions: impl IntoIterator<Item = &'a ForgeVersion>,
+        include_prereleases: bool,
+    ) -> Option<&'a ForgeVersion> {
+        self.filter(versions, include_prereleases).min()
+    }
 }
 
 impl ForgeVersionRangeIn {
@@ -47,9 +137,76 @@ impl ForgeVersionRangeIn {
         lower.matches(version, cmp::Ordering::Greater)
             && upper.matches(version, cmp::Ordering::Less)
     }
+
+    /// The lowest version allowed and whether it is inclusive, `None` if it is unbounded.
+    pub fn lower_bound(&self) -> Option<(&ForgeVersion, bool)> {
+        match self {
+            ForgeVersionRangeIn::Equal(version) => Some((version, true)),
+            ForgeVersionRangeIn::Range(lower, _) => lower.get(),
+        }
+    }
+
+    /// The highest version allowed and whether it is inclusive, `None` if it is unbounded.
+    pub fn upper_bound(&self) -> Option<(&ForgeVersion, bool)> {
+        match self {
+            ForgeVersionRangeIn::Equal(version) => Some((version, true)),
+            ForgeVersionRangeIn::Range(_, upper) => upper.get(),
+        }
+    }
+
+    pub fn is_satisfiable(&self) -> bool {
+        let ForgeVersionRangeIn::Range(lower, upper) = self else {
+            return true;
+        };
+
+        let (
+            Bound::Inclusive(Some(lo)) | Bound::Exclusive(Some(lo)),
+            Bound::Inclusive(Some(hi)) | Bound::Exclusive(Some(hi)),
+        ) = (lower, upper)
+        else {
+            return true;
+        };
+
+        match lo.cmp(hi) {
+            cmp::Ordering::Less => true,
+            cmp::Ordering::Equal => {
+                matches!((lower, upper), (Bound::Inclusive(_), Bound::Inclusive(_)))
+            }
+            cmp::Ordering::Greater => false,
+        }
+    }
+}
+
+impl ForgeVersionRangeIn {
+    /// Check if the version is not a prerelease, or a bound is a prerelease of the same release.
+    fn allows_prerelease(&self, version: &ForgeVersion) -> bool {
+        if !version.is_prerelease() {
+            return true;
+        }
+
+        [self.lower_bound(), self.upper_bound()]
+            .into_iter()
+            .flatten()
+            .map(|(v, _)| v)
+            .any(|v| v.is_prerelease() && cmp_list(&v.release(), &version.release()).is_eq())
+    }
 }
 
 impl Bound {
+    pub fn version(&self) -> Option<&ForgeVersion> {
+        match self {
+            Bound::Inclusive(version) | Bound::Exclusive(version) => version.as_ref(),
+        }
+    }
+
+    pub fn is_inclusive(&self) -> bool {
+        matches!(self, Bound::Inclusive(_))
+    }
+
+    fn get(&self) -> Option<(&ForgeVersion, bool)> {
+        self.version().map(|version| (version, self.is_inclusive()))
+    }
+
     fn matches(&self, version: &ForgeVersion, order: cmp::Ordering) -> bool {
         match self {
             Bound::Inclusive(Some(range)) => {
@@ -63,80 +220,106 @@ impl Bound {
 }
 
 impl ForgeVersionRange {
-    pub fn parse(mut s: &str) -> Result<Self> {
+    pub fn parse(input: &str) -> Result<Self> {
+        Self::parse_with(input, false).map(|(range, _)| range)
+    }
+
+    /// Like [`ForgeVersionRange::parse`], but like Maven does not need a `,` between sets,
+    /// e.g. `[1,2)[3,)`.
+    pub fn parse_lenient(input: &str) -> Result<(Self, Vec<Located<Warning>>)> {
+        Self::parse_with(input, true)
+    }
+
+    fn parse_with(input: &str, lenient: bool) -> Result<(Self, Vec<Located<Warning>>)> {
         let mut terms = vec![];
+        let mut warnings = vec![];
+        let mut s = input;
 
         loop {
-            s = s.trim();
-            let Some((version, rest)) = Self::parse_in(s)? else {
+            s = s.trim_start();
+            let is_list = s.starts_with(['[', '(']);
+            let Some((version, rest)) = Self::parse_in(input, s)? else {
                 break;
             };
             terms.push(version);
-            s = rest.trim();
+            s = rest.trim_start();
 
             if s.is_empty() {
                 break;
             }
 
-            let Some(rest) = take_ch(s, ',') else {
-                return Err(Error::Expected("','"));
-            };
-            s = rest;
+            match take_ch(s, ',') {
+                Some(rest) => s = rest,
+                None if lenient && is_list && s.starts_with(['[', '(']) => {
+                    let offset = input.len() - s.len();
+                    warnings.push(Located::new(Warning::MissingComma, input, offset, &s[..1]));
+                }
+                None => return Err(located(input, s, token_len(s), Error::Expected("','"))),
+            }
         }
 
-        Ok(Self(terms))
+        Ok((Self(terms), warnings))
     }
 
-    fn parse_in(input: &str) -> Result<Option<(ForgeVersionRangeIn, &str)>> {
-        let input = input.trim();
+    fn parse_in<'a>(input: &str, s: &'a str) -> Result<Option<(ForgeVersionRangeIn, &'a str)>> {
+        let s = s.trim_start();
 
-        if let (Some(version), input) = Self::parse_version(input)? {
+        if let (Some(version), s) = Self::parse_version(s)? {
             return Ok(Some((
                 ForgeVersionRangeIn::Range(Bound::Inclusive(Some(version)), Bound::Exclusive(None)),
-                input,
+                s,
             )));
         }
 
-        if let Some(res) = Self::parse_list(input)? {
+        if let Some(res) = Self::parse_list(input, s)? {
             return Ok(Some(res));
         }
 
         Ok(None)
     }
 
-    fn parse_list(input: &str) -> Result<Option<(ForgeVersionRangeIn, &str)>> {
-        let Some((open_inclusive, input)) = Self::parse_open(input) else {
+    fn parse_list<'a>(
+        input: &str,
+        list: &'a str,
+    ) -> Result<Option<(ForgeVersionRangeIn, &'a str)>> {
+        let Some((open_inclusive, s)) = Self::parse_open(list) else {
             return Ok(None);
         };
 
-        let (lower, input) = Self::parse_version(input)?;
+        let (lower, s) = Self::parse_version(s)?;
 
-        if let Some((close_inclusive, input)) = Self::parse_close(input) {
+        if let Some((close_inclusive, s)) = Self::parse_close(s) {
+            let whole = list.len() - s.len();
             return match lower {
                 Some(lower) => {
                     if open_inclusive && close_inclusive {
-                        Ok(Some((ForgeVersionRangeIn::Equal(lower), input)))
+                        Ok(Some((ForgeVersionRangeIn::Equal(lower), s)))
                     } else {
-                        Err(Error::BadParensOneItem)
+                        Err(located(input, list, whole, Error::BadParensOneItem))
                     }
                 }
-                None => Err(Error::EmptyList),
+                None => Err(located(input, list, whole, Error::EmptyList)),
             };
         }
 
-        let Some(input) = take_ch(input, ',') else {
-            return Err(Error::Expected("','"));
+        let Some(s) = take_ch(s, ',') else {
+            return Err(located(input, s, token_len(s), Error::Expected("','")));
         };
 
-        let (upper, input) = Self::parse_version(input)?;
-        let Some((close_inclusive, input)) = Self::parse_close(input) else {
-            return Err(Error::Expected("']' or ')'"));
+        let (upper, s) = Self::parse_version(s)?;
+        let Some((close_inclusive, s)) = Self::parse_close(s) else {
+            return Err(located(
+                input,
+                s,
+                token_len(s),
+                Error::Expected("']' or ')'"),
+            ));
         };
 
         let lower = Bound::from_bool(open_inclusive, lower);
         let upper = Bound::from_bool(close_inclusive, upper);
 
-        Ok(Some((ForgeVersionRangeIn::Range(lower, upper), input)))
+        Ok(Some((ForgeVersionRangeIn::Range(lower, upper), s)))
     }
 
     #[inline]
@@ -175,6 +358,24 @@ impl ForgeVersionRange {
     }
 }
 
+/// Wrap an error found at `at`, a suffix of `input`, spanning `len` bytes.
+fn located(input: &str, at: &str, len: usize, error: Error) -> Error {
+    let offset = input.len() - at.len();
+    Error::Located(Box::new(Located::new(error, input, offset, &at[..len])))
+}
+
+/// The length of a single delimiter or of the text up to the next one.
+fn token_len(s: &str) -> usize {
+    let is_delimiter = |ch: char| "[](),".contains(ch);
+    match s.chars().next() {
+        None => 0,
+        Some(ch) if is_delimiter(ch) => ch.len_utf8(),
+        Some(_) => s
+            .find(|ch: char| ch.is_whitespace() || is_delimiter(ch))
+            .unwrap_or(s.len()),
+    }
+}
+
 fn take_ch(s: &str, ch: char) -> Option<&str> {
     if s.starts_with(ch) {
         Some(&s[ch.len_utf8()..])
@@ -200,7 +401,7 @@ impl fmt::Display for ForgeVersionRange {
             write!(f, "{range}")?;
         }
         for range in iter {
-            write!(f, "{range}")?;
+            write!(f, ",{range}")?;
         }
         Ok(())
     }
@@ -244,9 +445,13 @@ impl fmt::Display for Bound {
 }
 
 /// A ComparableVersion from Maven used by Forge mods.
-/// Based off of the spec described [here](https://cwiki.apache.org/confluence/display/MAVENOLD/Versioning) and the source code provided [here](https://maven.apache.org/ref/3.0/maven-artifact/xref/org/apache/maven/artifact/versioning/ComparableVersion.html).
-#[derive(PartialEq, Eq, Clone)]
-pub struct ForgeVersion(Vec<Item>);
+/// Follows the Maven 3.9 implementation provided [here](https://github.com/apache/maven/blob/maven-3.9.x/maven-artifact/src/main/java/org/apache/maven/artifact/versioning/ComparableVersion.java),
+/// which is what FML uses through maven-artifact.
+#[derive(Clone)]
+pub struct ForgeVersion {
+    value: String,
+    items: Vec<Item>,
+}
 
 impl fmt::Debug for ForgeVersion {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -256,50 +461,145 @@ impl fmt::Debug for ForgeVersion {
 
 impl fmt::Display for ForgeVersion {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        for (i, item) in self.0.iter().enumerate() {
-            if i != 0 {
-                write!(f, ".")?;
-            }
-            match item {
-                Item::String(x) => write!(f, "{x}")?,
-                Item::Integer(x) => write!(f, "{x}")?,
-                Item::Dash => write!(f, "-")?,
-                Item::Null => write!(f, "<null>")?,
-            }
-        }
-        Ok(())
+        f.write_str(&self.value)
     }
 }
 
 impl ForgeVersion {
     pub fn parse(s: &str) -> Result<Self> {
-        Self::parse_from_chars(s.chars())
+        if s.trim().is_empty() {
+            return Err(Error::Empty);
+        }
+
+        Ok(Self {
+            value: s.to_string(),
+            items: Parser::parse(s),
+        })
     }
 
     pub fn parse_from_chars<I: Iterator<Item = char>>(iter: I) -> Result<Self> {
-        let mut parser = Parser::new();
-        for ch in iter {
-            parser.eat(ch)?;
+        Self::parse(&iter.collect::<String>())
+    }
+
+    /// The items after normalization, e.g. `1.0-alpha-1` is `1`, then a list of `alpha1`.
+    pub fn items(&self) -> &[Item] {
+        &self.items
+    }
+
+    /// Whether a qualifier makes it older than its release, e.g. `1.0-SNAPSHOT` or `1.0-rc1`,
+    /// but not `1.0-sp1`.
+    pub fn is_prerelease(&self) -> bool {
+        cmp_list(&self.items, &self.release()).is_lt()
+    }
+
+    /// The leading numbers, e.g. `1.0` of `1.0-SNAPSHOT`.
+    fn release(&self) -> Vec<Item> {
+        self.items
+            .iter()
+            .take_while(|item| matches!(item, Item::Integer(_)))
+            .cloned()
+            .collect()
+    }
+
+    /// Maven's canonical form, with aliases folded and trailing nulls trimmed,
+    /// e.g. `1.0.0-FINAL` is `1` and `1-cr-1` is `1-rc1`.
+    pub fn canonical(&self) -> String {
+        items_to_string(&canonical_items(&self.items))
+    }
+}
+
+impl ForgeVersion {
+    /// Increment the `index`-th of the leading dot separated numbers and reset the ones after it
+    /// to zero, keeping the qualifier, e.g. `bump(1)` of `1.20.1-SNAPSHOT` is `1.21.0-SNAPSHOT`.
+    pub fn bump(&self, index: usize) -> Self {
+        let (numbers, qualifier) = self.split_release();
+        let mut numbers: Vec<String> = numbers.into_iter().map(str::to_string).collect();
+        if numbers.len() <= index {
+            numbers.resize(index + 1, "0".to_string());
+        }
+        numbers[index] = increment_digits(&numbers[index]);
+        numbers[index + 1..].fill("0".to_string());
+        Self::from_text(numbers.join("."), qualifier)
+    }
+
+    /// Replace everything after the leading numbers, e.g. `1.0` with `SNAPSHOT` is `1.0-SNAPSHOT`.
+    pub fn with_qualifier(&self, qualifier: &str) -> Self {
+        let (numbers, _) = self.split_release();
+        Self::from_text(numbers.join("."), &format!("-{qualifier}"))
+    }
+
+    pub fn without_qualifier(&self) -> Self {
+        let (numbers, _) = self.split_release();
+        Self::from_text(numbers.join("."), "")
+    }
+
+    /// The leading dot separated numbers and the text after them,
+    /// a version without leading numbers is treated as `0`.
+    fn split_release(&self) -> (Vec<&str>, &str) {
+        let mut numbers = vec![];
+        let mut rest = self.value.trim();
+        loop {
+            let end = rest
+                .find(|c: char| !c.is_ascii_digit())
+                .unwrap_or(rest.len());
+            if end == 0 {
+                break;
+            }
+            numbers.push(&rest[..end]);
+            rest = &rest[end..];
+            match rest.strip_prefix('.') {
+                Some(next) if next.starts_with(|c: char| c.is_ascii_digit()) => rest = next,
+                _ => break,
+            }
+        }
+        if numbers.is_empty() {
+            numbers.push("0");
+        }
+        (numbers, rest)
+    }
+
+    fn from_text(release: String, qualifier: &str) -> Self {
+        let value = release + qualifier;
+        Self {
+            items: Parser::parse(&value),
+            value,
         }
-        Ok(Self(parser.consume()?))
     }
 }
 
-impl Ord for ForgeVersion {
-    fn cmp(&self, other: &Self) -> cmp::Ordering {
-        let iter = 0..self.0.len().max(other.0.len());
+/// Add one to a string of digits, keeping leading zeros, e.g. `09` is `10`.
+fn increment_digits(digits: &str) -> String {
+    let mut digits: Vec<char> = digits.chars().collect();
+    for digit in digits.iter_mut().rev() {
+        if *digit == '9' {
+            *digit = '0';
+        } else {
+            *digit = (*digit as u8 + 1) as char;
+            return digits.into_iter().collect();
+        }
+    }
+    digits.insert(0, '1');
+    digits.into_iter().collect()
+}
 
-        let mut iter = iter.map(|i| {
-            let a = self.0.get(i).unwrap_or(&Item::Null);
-            let b = other.0.get(i).unwrap_or(&Item::Null);
-            (a, b)
-        });
+/// Equal when they compare `Equal`, so `1`, `1.0` and `1-ga` are all equal.
+impl PartialEq for ForgeVersion {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other).is_eq()
+    }
+}
 
-        let Some((a, b)) = iter.next() else {
-            return cmp::Ordering::Equal;
-        };
+impl Eq for ForgeVersion {}
+
+impl Hash for ForgeVersion {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        canonical_items(&self.items).hash(state);
+    }
+}
 
-        a.cmp_list(b, iter)
+impl Ord for ForgeVersion {
+    fn cmp(&self, other: &Self) -> cmp::Ordering {
+        cmp_list(&self.items, &other.items)
     }
 }
 
@@ -309,182 +609,324 @@ impl PartialOrd for ForgeVersion {
     }
 }
 
-#[derive(PartialEq, Eq, Debug, Clone)]
-enum Item {
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
+pub enum Item {
+    /// Digits without leading zeros, so numbers of any size can be compared.
+    Integer(String),
     String(String),
-    Integer(u64),
-    Dash,
-    Null,
+    /// A qualifier followed by a number, like `alpha1` or `rc-2`.
+    Combination(String, String),
+    /// Items after a `-` or a number to letter transition.
+    List(Vec<Item>),
 }
 
 const QUALIFIERS: &[&str] = &["alpha", "beta", "milestone", "rc", "snapshot", "", "sp"];
-const ALIASES: &[(&str, &str)] = &[("ga", ""), ("final", ""), ("cr", "rc")];
+/// Sort and hash as a release, so `1-final` `==` `1`, but stay in [`ForgeVersion::items`] as written.
+const RELEASE_QUALIFIERS: &[&str] = &["ga", "final", "release"];
+const ALIASES: &[(&str, &str)] = &[("cr", "rc")];
 
 impl Item {
-    fn cmp_list<'a>(
-        &self,
-        other: &Self,
-        mut iter: impl Iterator<Item = (&'a Item, &'a Item)>,
-    ) -> cmp::Ordering {
-        match self.cmp_item(other) {
-            ItemOrdering::Ordering(cmp::Ordering::Equal) => {
-                let Some((a, b)) = iter.next() else {
-                    return cmp::Ordering::Equal;
-                };
-
-                Item::cmp_list(a, b, iter)
-            }
-            ItemOrdering::Ordering(cmp::Ordering::Less) => cmp::Ordering::Less,
-            ItemOrdering::Ordering(cmp::Ordering::Greater) => cmp::Ordering::Greater,
-            ItemOrdering::RecurseLeft => {
-                let Some((left, _)) = iter.next() else {
-                    return cmp::Ordering::Equal;
-                };
-
-                Item::cmp_list(left, &Item::Null, iter)
-            }
-            ItemOrdering::RecurseRight => {
-                let Some((_, right)) = iter.next() else {
-                    return cmp::Ordering::Equal;
-                };
+    fn integer(digits: &str) -> Self {
+        Item::Integer(strip_leading_zeros(digits))
+    }
 
-                Item::cmp_list(&Item::Null, right, iter)
-            }
+    fn string(value: &str) -> Self {
+        Item::String(qualifier(value, false))
+    }
+
+    fn combination(value: &str) -> Self {
+        let value = value.replace('-', "");
+        let index = value.find(|ch: char| ch.is_ascii_digit()).unwrap_or(0);
+        let (string, digits) = value.split_at(index);
+        Item::Combination(qualifier(string, true), strip_leading_zeros(digits))
+    }
+
+    fn is_null(&self) -> bool {
+        match self {
+            Item::Integer(digits) => digits == "0",
+            Item::String(value) => value.is_empty(),
+            Item::Combination(..) => false,
+            Item::List(items) => items.is_empty(),
         }
     }
 
-    fn cmp_item(&self, other: &Self) -> ItemOrdering {
-        ItemOrdering::Ordering(match (self, other) {
-            (Item::String(this), Item::String(other)) => cmp_string_string(this, other),
-            (Item::Integer(this), Item::Integer(other)) => this.cmp(other),
-            (Item::Null, Item::Null) => cmp::Ordering::Equal,
-            (Item::Dash, Item::Dash) => cmp::Ordering::Equal,
+    /// Compare with another item, or with nothing when the other version has no more items.
+    fn compare(&self, other: Option<&Item>) -> cmp::Ordering {
+        use cmp::Ordering::*;
 
-            // Integer is newer when comparing for string and dash.
-            (Item::String(_), Item::Integer(_)) => cmp::Ordering::Less,
-            (Item::Integer(_), Item::String(_)) => cmp::Ordering::Greater,
-            (Item::Dash, Item::Integer(_)) => cmp::Ordering::Less,
-            (Item::Integer(_), Item::Dash) => cmp::Ordering::Greater,
+        match (self, other) {
+            (Item::Integer(digits), None) => {
+                if digits == "0" {
+                    Equal
+                } else {
+                    Greater
+                }
+            }
+            (Item::Integer(this), Some(Item::Integer(other))) => cmp_digits(this, other),
+            (Item::Integer(_), Some(_)) => Greater,
+
+            (Item::String(value), None) => cmp_qualifier(value, ""),
+            (Item::String(_), Some(Item::Integer(_) | Item::List(_))) => Less,
+            (Item::String(this), Some(Item::String(other))) => cmp_qualifier(this, other),
+            // `sp` < `sp1`
+            (Item::String(this), Some(Item::Combination(other, _))) => {
+                cmp_qualifier(this, other).then(Less)
+            }
 
-            // Integer is newer when comparing null, or equal if the integer is 0
-            (Item::Integer(0), Item::Null) | (Item::Null, Item::Integer(0)) => cmp::Ordering::Equal,
-            (Item::Null, Item::Integer(_)) => cmp::Ordering::Less,
-            (Item::Integer(_), Item::Null) => cmp::Ordering::Greater,
+            (Item::Combination(value, _), None) => cmp_qualifier(value, ""),
+            (Item::Combination(..), Some(Item::Integer(_) | Item::List(_))) => Less,
+            (Item::Combination(this, _), Some(Item::String(other))) => {
+                cmp_qualifier(this, other).then(Greater)
+            }
+            (Item::Combination(this, a), Some(Item::Combination(other, b))) => {
+                cmp_qualifier(this, other).then_with(|| cmp_digits(a, b))
+            }
 
-            // List is newer when comparing string.
-            (Item::String(_), Item::Dash) => cmp::Ordering::Less,
-            (Item::Dash, Item::String(_)) => cmp::Ordering::Greater,
+            (Item::List(items), None) => items
+                .iter()
+                .map(|item| item.compare(None))
+                .find(|ord| ord.is_ne())
+                .unwrap_or(Equal),
+            (Item::List(_), Some(Item::Integer(_))) => Less,
+            (Item::List(_), Some(Item::String(_) | Item::Combination(..))) => Greater,
+            (Item::List(this), Some(Item::List(other))) => cmp_list(this, other),
+        }
+    }
+}
 
-            // String is newer when comparing null.
-            (Item::Null, Item::String(s)) => cmp_string_string("", s),
-            (Item::String(s), Item::Null) => cmp_string_string(s, ""),
+/// Fold release qualifiers into `""` and trim every trailing item that compares `Equal` to nothing,
+/// so versions that compare `Equal` have the same canonical items.
+fn canonical_items(items: &[Item]) -> Vec<Item> {
+    let fold = |value: &str| {
+        if RELEASE_QUALIFIERS.contains(&value) {
+            String::new()
+        } else {
+            value.to_string()
+        }
+    };
 
-            (Item::Dash, Item::Null) => return ItemOrdering::RecurseLeft,
-            (Item::Null, Item::Dash) => return ItemOrdering::RecurseRight,
+    let mut items: Vec<Item> = items
+        .iter()
+        .map(|item| match item {
+            Item::String(value) => Item::String(fold(value)),
+            Item::Combination(value, digits) => Item::Combination(fold(value), digits.clone()),
+            Item::List(items) => Item::List(canonical_items(items)),
+            Item::Integer(digits) => Item::Integer(digits.clone()),
         })
+        .collect();
+
+    while items.last().is_some_and(|item| item.compare(None).is_eq()) {
+        items.pop();
     }
+    items
 }
 
-enum ItemOrdering {
-    Ordering(cmp::Ordering),
-    RecurseLeft,
-    RecurseRight,
+fn items_to_string(items: &[Item]) -> String {
+    let mut s = String::new();
+    for item in items {
+        if !s.is_empty() {
+            s.push(if matches!(item, Item::List(_)) {
+                '-'
+            } else {
+                '.'
+            });
+        }
+        match item {
+            Item::Integer(digits) => s.push_str(digits),
+            Item::String(value) => s.push_str(value),
+            Item::Combination(value, digits) => {
+                s.push_str(value);
+                s.push_str(digits);
+            }
+            Item::List(items) => s.push_str(&items_to_string(items)),
+        }
+    }
+    s
 }
 
-/// Compare to strings, handle special qualifiers like `alpha` or `rc`
-fn cmp_string_string(this: &str, other: &str) -> cmp::Ordering {
-    fn to_alias(s: &str) -> &str {
-        match ALIASES.iter().find(|(alias, _)| *alias == s) {
-            Some((_, s)) => s,
-            None => s,
+fn cmp_list(this: &[Item], other: &[Item]) -> cmp::Ordering {
+    for i in 0..this.len().max(other.len()) {
+        let ord = match (this.get(i), other.get(i)) {
+            (Some(a), b) => a.compare(b),
+            (None, Some(b)) => b.compare(None).reverse(),
+            (None, None) => unreachable!(),
+        };
+        if ord.is_ne() {
+            return ord;
         }
     }
+    cmp::Ordering::Equal
+}
 
-    let (this, other) = (to_alias(this), to_alias(other));
+fn cmp_digits(this: &str, other: &str) -> cmp::Ordering {
+    this.len().cmp(&other.len()).then_with(|| this.cmp(other))
+}
 
-    let this_idx = QUALIFIERS.iter().position(|x| *x == this);
-    let other_idx = QUALIFIERS.iter().position(|x| *x == other);
+/// Known qualifiers are ordered by [`QUALIFIERS`], unknown ones come after them
+/// and are compared lexically.
+fn cmp_qualifier(this: &str, other: &str) -> cmp::Ordering {
+    fn comparable(qualifier: &str) -> (usize, &str) {
+        let qualifier = if RELEASE_QUALIFIERS.contains(&qualifier) {
+            ""
+        } else {
+            qualifier
+        };
+        match QUALIFIERS.iter().position(|x| *x == qualifier) {
+            Some(i) => (i, ""),
+            None => (QUALIFIERS.len(), qualifier),
+        }
+    }
 
-    match (this_idx, other_idx) {
-        // Qualifier is less than no qualifier
-        (Some(_), None) => return cmp::Ordering::Less,
-        (None, Some(_)) => return cmp::Ordering::Greater,
-        // Compare indicies
-        (Some(a), Some(b)) => match a.cmp(&b) {
-            cmp::Ordering::Equal => {}
-            ord => return ord,
-        },
-        (None, None) => {}
+    comparable(this).cmp(&comparable(other))
+}
+
+/// Resolve aliases, and `a1`, `b1` and `m1` as `alpha-1`, `beta-1` and `milestone-1`.
+fn qualifier(value: &str, followed_by_digit: bool) -> String {
+    let value = match value {
+        "a" if followed_by_digit => "alpha",
+        "b" if followed_by_digit => "beta",
+        "m" if followed_by_digit => "milestone",
+        value => value,
     };
 
-    this.cmp(other)
+    match ALIASES.iter().find(|(alias, _)| *alias == value) {
+        Some((_, value)) => value.to_string(),
+        None => value.to_string(),
+    }
 }
 
+fn strip_leading_zeros(digits: &str) -> String {
+    match digits.trim_start_matches('0') {
+        "" => "0".to_string(),
+        digits => digits.to_string(),
+    }
+}
+
+/// Every new list is appended to the end of the current one, which it then replaces,
+/// so the nesting is a chain that is only folded into items at the end.
 struct Parser {
-    buf: String,
-    last_char: Option<char>,
-    data: Vec<Item>,
+    chars: Vec<char>,
+    lists: Vec<Vec<Item>>,
+    is_digit: bool,
+    is_combination: bool,
+    start: usize,
 }
 
 impl Parser {
-    pub fn new() -> Self {
-        Self {
-            buf: String::new(),
-            last_char: None,
-            data: vec![],
+    fn parse(version: &str) -> Vec<Item> {
+        let mut parser = Self {
+            chars: version.to_lowercase().chars().collect(),
+            lists: vec![vec![]],
+            is_digit: false,
+            is_combination: false,
+            start: 0,
+        };
+
+        for i in 0..parser.chars.len() {
+            parser.eat(i);
         }
+        parser.consume()
     }
 
-    pub fn eat(&mut self, ch: char) -> Result<()> {
-        match ch {
-            '.' => self.push()?,
+    fn eat(&mut self, i: usize) {
+        match self.chars[i] {
+            '.' => {
+                self.push_item(i);
+                self.is_combination = false;
+                self.start = i + 1;
+            }
             '-' => {
-                self.push()?;
-                self.data.push(Item::Dash);
+                // `X-1` is treated as `X1`.
+                if i != self.start
+                    && !self.is_digit
+                    && self.chars.get(i + 1).is_some_and(char::is_ascii_digit)
+                {
+                    self.is_combination = true;
+                    return;
+                }
+                self.push_item(i);
+                self.start = i + 1;
+                self.lists.push(vec![]);
+                self.is_combination = false;
             }
-            ch => {
-                if self.last_char.is_some_and(|ch| ch.is_numeric()) == ch.is_numeric() {
-                    self.buf.push(ch);
-                } else {
-                    self.push()?;
-                    self.buf.push(ch);
+            '0'..='9' => {
+                if !self.is_digit && i > self.start {
+                    self.is_combination = true;
+                    if !self.current().is_empty() {
+                        self.lists.push(vec![]);
+                    }
                 }
+                self.is_digit = true;
             }
-        };
-        self.last_char = Some(ch);
-        Ok(())
+            _ => {
+                if self.is_digit && i > self.start {
+                    self.push_item(i);
+                    self.start = i;
+                    self.lists.push(vec![]);
+                    self.is_combination = false;
+                }
+                self.is_digit = false;
+            }
+        }
     }
 
-    fn consume(mut self) -> Result<Vec<Item>> {
-        self.push()?;
-        if self.data.is_empty() {
-            return Err(Error::Empty);
+    fn consume(mut self) -> Vec<Item> {
+        if self.chars.len() > self.start {
+            // Treat `.X` as `-X` so `1.0.0.X1` < `1.0.0-X2`.
+            if !self.is_digit && !self.current().is_empty() {
+                self.lists.push(vec![]);
+            }
+            self.push_item(self.chars.len());
         }
-        Ok(self.data)
+
+        let mut items = self.lists.pop().expect("there is always a list");
+        normalize(&mut items);
+        while let Some(mut parent) = self.lists.pop() {
+            parent.push(Item::List(items));
+            normalize(&mut parent);
+            items = parent;
+        }
+        items
     }
 
-    fn push(&mut self) -> Result<()> {
-        let buf = mem::take(&mut self.buf);
+    fn current(&mut self) -> &mut Vec<Item> {
+        self.lists.last_mut().expect("there is always a list")
+    }
 
-        match buf.parse() {
-            Ok(value) => {
-                self.data.push(Item::Integer(value));
-                return Ok(());
+    /// Push the item from the start of the buffer up to `end`, or `0` if it is empty.
+    fn push_item(&mut self, end: usize) {
+        let item = if end == self.start {
+            Item::integer("0")
+        } else {
+            let buf: String = self.chars[self.start..end].iter().collect();
+            if self.is_combination {
+                Item::combination(&buf)
+            } else if self.is_digit {
+                Item::integer(&buf)
+            } else {
+                Item::string(&buf)
             }
-            Err(e) => match e.kind() {
-                std::num::IntErrorKind::PosOverflow => return Err(Error::IntPosOverflow(buf)),
-                std::num::IntErrorKind::NegOverflow => return Err(Error::IntNegOverflow(buf)),
-                _ => {}
-            },
-        }
-
-        let buf = buf.trim().to_string();
+        };
+        self.current().push(item);
+    }
+}
 
-        if !buf.is_empty() {
-            self.data.push(Item::String(buf));
+/// Remove null items that make no difference, so `1.0.0` is `1` and `1.0-a` is `1-a`.
+fn normalize(items: &mut Vec<Item>) {
+    for i in (0..items.len()).rev() {
+        if !items[i].is_null() {
+            continue;
+        }
+        let remove = match items.get(i + 1) {
+            None | Some(Item::String(_)) => true,
+            Some(Item::List(next)) => {
+                matches!(next.first(), Some(Item::String(_) | Item::Combination(..)))
+            }
+            Some(_) => false,
+        };
+        if remove {
+            items.remove(i);
         }
-
-        Ok(())
     }
 }
 
@@ -492,8 +934,10 @@ pub type Result<T> = std::result::Result<T, Error>;
 
 #[derive(thiserror::Error, Debug)]
 pub enum Error {
+    #[deprecated(note = "numbers of any size are accepted")]
     #[error("number too big: {0}")]
     IntPosOverflow(String),
+    #[deprecated(note = "numbers of any size are accepted")]
     #[error("number too small: {0}")]
     IntNegOverflow(String),
     #[error("input is empty")]
@@ -504,10 +948,14 @@ pub enum Error {
     BadParensOneItem,
     #[error("expected {0}")]
     Expected(&'static str),
+    #[error(transparent)]
+    Located(Box<Located<Error>>),
 }
 
 #[cfg(test)]
 mod test {
+    use std::collections::HashSet;
+
     use super::*;
 
     #[test]
@@ -555,6 +1003,19 @@ mod test {
         assert!(!range.matches(&ForgeVersion::parse("1.2").unwrap()));
     }
 
+    #[test]
+    fn satisfiable_version_range() {
+        for s in ["[1.0,2)", "[1,1.0]", "(,1]", "(2,1),[3]"] {
+            assert!(ForgeVersionRange::parse(s).unwrap().is_satisfiable(), "{s}");
+        }
+        for s in ["(2,1)", "[1.0,1)", "(1,1]", "[2,1.0]", ""] {
+            assert!(
+                !ForgeVersionRange::parse(s).unwrap().is_satisfiable(),
+                "{s}"
+            );
+        }
+    }
+
     #[test]
     fn parse_version_range() {
         assert_eq!(
@@ -603,14 +1064,154 @@ mod test {
         );
         assert!(matches!(
             ForgeVersionRange::parse(" [ ] ").unwrap_err(),
-            Error::EmptyList
+            Error::Located(e) if matches!(e.error, Error::EmptyList)
         ));
         assert!(matches!(
             ForgeVersionRange::parse("[1.0)").unwrap_err(),
-            Error::BadParensOneItem
+            Error::Located(e) if matches!(e.error, Error::BadParensOneItem)
         ));
     }
 
+    #[test]
+    fn range_error_location() {
+        let located = |s: &str| match ForgeVersionRange::parse(s).unwrap_err() {
+            Error::Located(e) => *e,
+            e => panic!("{e} has no location"),
+        };
+
+        let e = located("[1.0,2.0]]");
+        assert_eq!((e.offset, e.token.as_str()), (9, "]"));
+        assert_eq!(e.to_string(), "expected ',' at `]` (offset 9)");
+        assert_eq!(e.underline(), "[1.0,2.0]]\n         ^");
+
+        let e = located("[1.0,2.0] 3.0");
+        assert_eq!((e.offset, e.token.as_str()), (10, "3.0"));
+
+        let e = located("[1.20,1.21),(1.0)");
+        assert!(matches!(e.error, Error::BadParensOneItem));
+        assert_eq!(e.underline(), "[1.20,1.21),(1.0)\n            ^^^^^");
+
+        let e = located("[1,");
+        assert_eq!((e.offset, e.token.as_str()), (3, ""));
+        assert_eq!(e.to_string(), "expected ']' or ')' at end of input");
+    }
+
+    #[test]
+    fn parse_lenient_range() {
+        let (range, warnings) = ForgeVersionRange::parse_lenient("[1,2) [3,)").unwrap();
+        assert_eq!(range, ForgeVersionRange::parse("[1,2),[3,)").unwrap());
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(
+            (warnings[0].error, warnings[0].offset),
+            (Warning::MissingComma, 6)
+        );
+        assert!(ForgeVersionRange::parse("[1,2) [3,)").is_err());
+
+        // Maven only allows this between sets.
+        assert!(ForgeVersionRange::parse_lenient("[1,2) 3").is_err());
+        assert!(
+            ForgeVersionRange::parse_lenient("[1,2],3")
+                .unwrap()
+                .1
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn display_version_range() {
+        for s in ["[1.0]", "[1.0,)", "(,1.0],[1.2,)", "[1-SNAPSHOT,2)"] {
+            assert_eq!(ForgeVersionRange::parse(s).unwrap().to_string(), s);
+        }
+        assert_eq!(
+            ForgeVersion::parse("1-alpha10-SNAPSHOT")
+                .unwrap()
+                .to_string(),
+            "1-alpha10-SNAPSHOT"
+        );
+    }
+
+    #[test]
+    fn satisfying_versions() {
+        let versions: Vec<ForgeVersion> = [
+            "1.0",
+            "1.1",
+            "1.2-SNAPSHOT",
+            "1.2-rc1",
+            "1.2",
+            "1.2-sp1",
+            "2.0-alpha",
+            "2.0",
+        ]
+        .into_iter()
+        .map(|s| ForgeVersion::parse(s).unwrap())
+        .collect();
+        let range = |s| ForgeVersionRange::parse(s).unwrap();
+        let to_string = |v: Option<&ForgeVersion>| v.map(|v| v.to_string());
+
+        let stable = range("[1.0,2.0)");
+        assert_eq!(
+            to_string(stable.max_satisfying(&versions, false)).as_deref(),
+            Some("1.2-sp1")
+        );
+        assert_eq!(
+            to_string(stable.max_satisfying(&versions, true)).as_deref(),
+            Some("2.0-alpha")
+        );
+        assert_eq!(
+            to_string(stable.min_satisfying(&versions, false)).as_deref(),
+            Some("1.0")
+        );
+
+        // Only prereleases of the release mentioned in the range are opted in,
+        // Maven orders `SNAPSHOT` after `rc`.
+        let filtered: Vec<String> = range("[1.2-rc1,)")
+            .filter(&versions, false)
+            .map(|v| v.to_string())
+            .collect();
+        assert_eq!(
+            filtered,
+            vec!["1.2-SNAPSHOT", "1.2-rc1", "1.2", "1.2-sp1", "2.0"]
+        );
+
+        assert_eq!(range("[3,)").max_satisfying(&versions, true), None);
+    }
+
+    #[test]
+    fn inspect_range() {
+        let range = ForgeVersionRange::parse("[1.0],(1.2,2.0],[3,)").unwrap();
+        let restrictions = range.restrictions();
+        assert_eq!(restrictions.len(), 3);
+        assert!(matches!(restrictions[0], ForgeVersionRangeIn::Equal(_)));
+
+        let bound = |bound: Option<(&ForgeVersion, bool)>| {
+            bound.map(|(v, inclusive)| (v.to_string(), inclusive))
+        };
+        assert_eq!(
+            bound(restrictions[1].lower_bound()),
+            Some(("1.2".into(), false))
+        );
+        assert_eq!(
+            bound(restrictions[1].upper_bound()),
+            Some(("2.0".into(), true))
+        );
+        assert_eq!(bound(restrictions[2].upper_bound()), None);
+        assert_eq!(bound(range.lower_bound()), Some(("1.0".into(), true)));
+        assert_eq!(bound(range.upper_bound()), None);
+
+        let range = ForgeVersionRange::parse("(2,1),[1.0,1.5),(1.0,2]").unwrap();
+        assert_eq!(bound(range.lower_bound()), Some(("1.0".into(), true)));
+        assert_eq!(bound(range.upper_bound()), Some(("2".into(), true)));
+
+        let ForgeVersionRangeIn::Range(lower, _) = &range.restrictions()[1] else {
+            panic!("expected a range");
+        };
+        assert!(lower.is_inclusive());
+        assert_eq!(lower.version().unwrap().to_string(), "1.0");
+
+        let version = ForgeVersion::parse("1.0-alpha-1").unwrap();
+        assert_eq!(version.items()[0], Item::Integer("1".into()));
+    }
+
     #[test]
     fn compare_comparable_version() {
         cmp_str("1.0", "1.0-alpha", cmp::Ordering::Greater);
@@ -623,50 +1224,290 @@ mod test {
         cmp_str("1.0-alpha-1.2", "1.0-alpha-2", cmp::Ordering::Less);
     }
 
+    #[test]
+    fn bump_version() {
+        let version = |s: &str| ForgeVersion::parse(s).unwrap();
+        assert_eq!(
+            version("1.20.1-SNAPSHOT").bump(1).to_string(),
+            "1.21.0-SNAPSHOT"
+        );
+        assert_eq!(
+            version("1.20.1-47.1.0").bump(2).to_string(),
+            "1.20.2-47.1.0"
+        );
+        assert_eq!(version("1.0").bump(3).to_string(), "1.0.0.1");
+        assert_eq!(version("1.9.99").bump(2).to_string(), "1.9.100");
+        assert_eq!(version("1.09").bump(1).to_string(), "1.10");
+        assert_eq!(version("2.0a1").bump(0).to_string(), "3.0a1");
+        assert_eq!(version("SNAPSHOT").bump(0).to_string(), "1SNAPSHOT");
+        assert!(version("1.20.1").bump(0) > version("1.20.1"));
+
+        assert_eq!(version("1.0").with_qualifier("rc1").to_string(), "1.0-rc1");
+        assert!(version("1.0").with_qualifier("rc1").is_prerelease());
+        assert_eq!(
+            version("1.2.3-beta-2").with_qualifier("beta-3").to_string(),
+            "1.2.3-beta-3"
+        );
+        assert_eq!(
+            version("1.2.3-beta-2").without_qualifier().to_string(),
+            "1.2.3"
+        );
+        assert_eq!(version("1.2.3.").without_qualifier().to_string(), "1.2.3");
+    }
+
     #[test]
     fn parse_comparable_version() {
-        let items = parse_str("1.0");
-        assert_eq!(items, vec![Item::Integer(1), Item::Integer(0)]);
-        let items = parse_str("1.0.1");
+        let int = |s: &str| Item::Integer(s.into());
+        let string = |s: &str| Item::String(s.into());
+
+        assert_eq!(parse_str("1.0"), vec![int("1")]);
+        assert_eq!(parse_str("1.0.1"), vec![int("1"), int("0"), int("1")]);
         assert_eq!(
-            items,
-            vec![Item::Integer(1), Item::Integer(0), Item::Integer(1)]
+            parse_str("1-SNAPSHOT"),
+            vec![int("1"), Item::List(vec![string("snapshot")])]
         );
-        let items = parse_str("1-SNAPSHOT");
         assert_eq!(
-            items,
+            parse_str("1-alpha10-SNAPSHOT"),
             vec![
-                Item::Integer(1),
-                Item::Dash,
-                Item::String("SNAPSHOT".into())
+                int("1"),
+                Item::List(vec![
+                    Item::Combination("alpha".into(), "10".into()),
+                    Item::List(vec![string("snapshot")]),
+                ]),
             ]
         );
-        let items = parse_str("1-alpha10-SNAPSHOT");
         assert_eq!(
-            items,
+            parse_str("1.0.final-redhat"),
             vec![
-                Item::Integer(1),
-                Item::Dash,
-                Item::String("alpha".into()),
-                Item::Integer(10),
-                Item::Dash,
-                Item::String("SNAPSHOT".into()),
+                int("1"),
+                string("final"),
+                Item::List(vec![string("redhat")])
             ]
         );
+        assert_eq!(
+            parse_str("00012345678901234567890"),
+            vec![int("12345678901234567890")]
+        );
     }
 
-    fn parse_str(s: &str) -> Vec<Item> {
-        let mut parser = Parser::new();
-        for ch in s.chars() {
-            parser.eat(ch).unwrap();
+    // Test vectors from Maven's `ComparableVersionTest`.
+    const VERSIONS_QUALIFIER: &[&str] = &[
+        "1-alpha2snapshot",
+        "1-alpha2",
+        "1-alpha-123",
+        "1-beta-2",
+        "1-beta123",
+        "1-m2",
+        "1-m11",
+        "1-rc",
+        "1-cr2",
+        "1-rc123",
+        "1-SNAPSHOT",
+        "1",
+        "1-sp",
+        "1-sp2",
+        "1-sp123",
+        "1-abc",
+        "1-def",
+        "1-pom-1",
+        "1-1-snapshot",
+        "1-1",
+        "1-2",
+        "1-123",
+    ];
+
+    const VERSIONS_NUMBER: &[&str] = &[
+        "2.0", "2.0.a", "2-1", "2.0.2", "2.0.123", "2.1.0", "2.1-a", "2.1b", "2.1-c", "2.1-1",
+        "2.1.0.1", "2.2", "2.123", "11.a2", "11.a11", "11.b2", "11.b11", "11.m2", "11.m11", "11",
+        "11.a", "11b", "11c", "11m",
+    ];
+
+    fn check_versions_order(versions: &[&str]) {
+        for (i, low) in versions.iter().enumerate() {
+            for high in &versions[i + 1..] {
+                cmp_str(low, high, cmp::Ordering::Less);
+            }
+        }
+    }
+
+    fn check_versions_equal(a: &str, b: &str) {
+        cmp_str(a, b, cmp::Ordering::Equal);
+        assert_eq!(
+            ForgeVersion::parse(a).unwrap(),
+            ForgeVersion::parse(b).unwrap()
+        );
+    }
+
+    #[test]
+    fn maven_versions_order() {
+        check_versions_order(VERSIONS_QUALIFIER);
+        check_versions_order(VERSIONS_NUMBER);
+
+        for pair in [
+            ["1", "2"],
+            ["1.5", "2"],
+            ["1", "2.5"],
+            ["1.0", "1.1"],
+            ["1.1", "1.2"],
+            ["1.0.0", "1.1"],
+            ["1.0.1", "1.1"],
+            ["1.1", "1.2.0"],
+            ["1.0-alpha-1", "1.0"],
+            ["1.0-alpha-1", "1.0-alpha-2"],
+            ["1.0-alpha-1", "1.0-beta-1"],
+            ["1.0-beta-1", "1.0-SNAPSHOT"],
+            ["1.0-SNAPSHOT", "1.0"],
+            ["1.0-alpha-1-SNAPSHOT", "1.0-alpha-1"],
+            ["1.0", "1.0-1"],
+            ["1.0-1", "1.0-2"],
+            ["1.0.0", "1.0-1"],
+            ["2.0-1", "2.0.1"],
+            ["2.0.1-klm", "2.0.1-lmn"],
+            ["2.0.1", "2.0.1-xyz"],
+            ["2.0.1", "2.0.1-123"],
+            ["2.0.1-xyz", "2.0.1-123"],
+            ["0.7", "2"],
+            ["0.2", "1.0.7"],
+            // Digits are newer than letters.
+            ["J", "7"],
+            ["c", "7"],
+            ["aardvark", "zebra"],
+        ] {
+            check_versions_order(&pair);
+        }
+
+        // MNG-5568
+        check_versions_order(&["6.1.0rc3", "6.1.0", "6.1H.5-beta"]);
+        // MNG-6572
+        check_versions_order(&[
+            "20190126.230843",
+            "1234567890.12345",
+            "123456789012345.1H.5-beta",
+            "12345678901234567890.1H.5-beta",
+        ]);
+        // MNG-6964
+        check_versions_order(&["1-0.alpha", "1-0.beta", "1"]);
+        // MNG-7714
+        for sp in ["1.0-sp1-redhat", "1.0-sp-1-redhat", "1.0-sp.1-redhat"] {
+            check_versions_order(&["1.0.final-redhat", sp]);
+        }
+    }
+
+    #[test]
+    fn maven_versions_equal() {
+        for [a, b] in [
+            ["1", "1"],
+            ["1", "1.0"],
+            ["1", "1.0.0"],
+            ["1.0", "1.0.0"],
+            ["1", "1-0"],
+            ["1", "1.0-0"],
+            ["1.0", "1.0-0"],
+            ["1a", "1-a"],
+            ["1a", "1.0-a"],
+            ["1a", "1.0.0-a"],
+            ["1.0a", "1-a"],
+            ["1.0.0a", "1-a"],
+            ["1x", "1-x"],
+            ["1x", "1.0-x"],
+            ["1x", "1.0.0-x"],
+            ["1.0x", "1-x"],
+            ["1.0.0x", "1-x"],
+            ["1cr", "1rc"],
+            ["1a1", "1-alpha-1"],
+            ["1b2", "1-beta-2"],
+            ["1m3", "1-milestone-3"],
+            ["1X", "1x"],
+            ["1A", "1a"],
+            ["1B", "1b"],
+            ["1M", "1m"],
+            ["1Cr", "1Rc"],
+            ["1cR", "1rC"],
+            ["1m3", "1Milestone3"],
+            ["1m3", "1MileStone3"],
+            ["1m3", "1MILESTONE3"],
+            ["0000000000000000001", "1"],
+            ["000000000000000000000000000000000000", "0"],
+        ] {
+            check_versions_equal(a, b);
+        }
+
+        // MNG-7644
+        for x in [
+            "abc",
+            "alpha",
+            "a",
+            "beta",
+            "b",
+            "def",
+            "milestone",
+            "m",
+            "RC",
+        ] {
+            check_versions_order(&[&format!("1.0.0.{x}1"), &format!("1.0.0-{x}2")]);
+            check_versions_equal(&format!("2-{x}"), &format!("2.0.{x}"));
+            check_versions_equal(&format!("2-{x}"), &format!("2.0.0.{x}"));
+            check_versions_equal(&format!("2.0.{x}"), &format!("2.0.0.{x}"));
+        }
+    }
+
+    #[test]
+    fn canonical_version() {
+        for (s, canonical) in [
+            ("0.x", "x"),
+            ("0.2", "0.2"),
+            ("1.0.0", "1"),
+            ("1.0.0-FINAL", "1"),
+            ("1-cr-1", "1-rc1"),
+            ("1.0-alpha10-SNAPSHOT", "1-alpha10-snapshot"),
+            ("1.2.3-SNAPSHOT", "1.2.3-snapshot"),
+        ] {
+            assert_eq!(
+                ForgeVersion::parse(s).unwrap().canonical(),
+                canonical,
+                "{s}"
+            );
+        }
+    }
+
+    #[test]
+    fn hash_agrees_with_ord() {
+        let versions: HashSet<ForgeVersion> = ["1", "1.0", "1.0.0", "1-ga", "1.0-FINAL", "1-0"]
+            .into_iter()
+            .map(|s| ForgeVersion::parse(s).unwrap())
+            .collect();
+        assert_eq!(versions.len(), 1);
+
+        let versions: HashSet<ForgeVersion> = ["1-rc1", "1-cr-1", "1.0-rc-1", "1-rc2"]
+            .into_iter()
+            .map(|s| ForgeVersion::parse(s).unwrap())
+            .collect();
+        assert_eq!(versions.len(), 2);
+    }
+
+    #[test]
+    fn maven_release_qualifiers() {
+        // Kept as items like Maven does, but ordered the same as a release.
+        for s in [
+            "1ga", "1release", "1final", "1Ga", "1GA", "1RELeaSE", "1FINAL",
+        ] {
+            assert_ne!(parse_str(s), parse_str("1"));
+            check_versions_equal(s, "1");
         }
-        parser.consume().unwrap()
+    }
+
+    fn parse_str(s: &str) -> Vec<Item> {
+        ForgeVersion::parse(s).unwrap().items
     }
 
     fn cmp_str(a: &str, b: &str, ord: cmp::Ordering) {
-        let ord_a = ForgeVersion(parse_str(a)).cmp(&ForgeVersion(parse_str(b)));
-        let ord_b = ForgeVersion(parse_str(b)).cmp(&ForgeVersion(parse_str(a)));
-        assert_eq!(ord_a, ord_b.reverse());
-        assert_eq!(ord_a, ord);
+        let (a_version, b_version) = (
+            ForgeVersion::parse(a).unwrap(),
+            ForgeVersion::parse(b).unwrap(),
+        );
+        let ord_a = a_version.cmp(&b_version);
+        let ord_b = b_version.cmp(&a_version);
+        assert_eq!(ord_a, ord_b.reverse(), "{a} {b}");
+        assert_eq!(ord_a, ord, "{a} {b}");
     }
 }
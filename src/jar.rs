@@ -0,0 +1,359 @@
+use std::{
+    fs,
+    io::{self, Read, Seek},
+    path::Path,
+};
+
+use serde::Serialize;
+use zip::{ZipArchive, result::ZipError};
+
+use crate::{
+    fabric::FabricManifest,
+    forge::{
+        self, UnsubstitutedForgeManifest,
+        mcmod_info::{self, McModInfo},
+        version::ForgeVersion,
+    },
+    jar_manifest::{self, ForgeAttributes, JarManifest},
+    quilt::QuiltManifest,
+};
+
+pub const FABRIC_MANIFEST: &str = "fabric.mod.json";
+pub const QUILT_MANIFEST: &str = "quilt.mod.json";
+pub const FORGE_MANIFEST: &str = "META-INF/mods.toml";
+pub const NEOFORGE_MANIFEST: &str = "META-INF/neoforge.mods.toml";
+pub const MCMOD_INFO: &str = "mcmod.info";
+pub const JAR_MANIFEST: &str = "META-INF/MANIFEST.MF";
+
+/// A mod jar file.
+pub struct Jar<R> {
+    archive: ZipArchive<R>,
+}
+
+impl Jar<fs::File> {
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        Self::new(fs::File::open(path)?)
+    }
+}
+
+impl<R: Read + Seek> Jar<R> {
+    pub fn new(reader: R) -> Result<Self> {
+        Ok(Self {
+            archive: ZipArchive::new(reader)?,
+        })
+    }
+
+    /// Read a file from the jar, returns `None` if the file does not exist.
+    pub fn read(&mut self, name: &str) -> Result<Option<Vec<u8>>> {
+        let mut file = match self.archive.by_name(name) {
+            Ok(file) => file,
+            Err(ZipError::FileNotFound) => return Ok(None),
+            Err(e) => return Err(e.into()),
+        };
+
+        let mut buf = vec![];
+        file.read_to_end(&mut buf)?;
+        Ok(Some(buf))
+    }
+
+    /// Read a UTF-8 file from the jar, returns `None` if the file does not exist.
+    pub fn read_to_string(&mut self, name: &str) -> Result<Option<String>> {
+        match self.read(name)? {
+            Some(buf) => Ok(Some(String::from_utf8(buf)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Parse the `fabric.mod.json` in the jar.
+    pub fn fabric_manifest(&mut self) -> Result<Option<FabricManifest>> {
+        match self.read(FABRIC_MANIFEST)? {
+            Some(buf) => Ok(Some(serde_json::from_slice(&buf)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Parse the `META-INF/mods.toml` in the jar.
+    pub fn forge_manifest(&mut self) -> Result<Option<UnsubstitutedForgeManifest>> {
+        match self.read_to_string(FORGE_MANIFEST)? {
+            Some(s) => Ok(Some(toml::from_str(&s)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Parse the `quilt.mod.json` in the jar.
+    pub fn quilt_manifest(&mut self) -> Result<Option<QuiltManifest>> {
+        match self.read(QUILT_MANIFEST)? {
+            Some(buf) => Ok(Some(serde_json::from_slice(&buf).map_err(Error::Quilt)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Parse the `META-INF/neoforge.mods.toml` in the jar.
+    pub fn neoforge_manifest(&mut self) -> Result<Option<UnsubstitutedForgeManifest>> {
+        match self.read_to_string(NEOFORGE_MANIFEST)? {
+            Some(s) => Ok(Some(toml::from_str(&s).map_err(Error::NeoForge)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Parse the `mcmod.info` in the jar.
+    pub fn mcmod_info(&mut self) -> Result<Option<Vec<McModInfo>>> {
+        match self.read_to_string(MCMOD_INFO)? {
+            Some(s) => Ok(Some(mcmod_info::parse(&s).map_err(Error::McModInfo)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Parse the `META-INF/MANIFEST.MF` in the jar.
+    pub fn jar_manifest(&mut self) -> Result<Option<JarManifest>> {
+        match self.read_to_string(JAR_MANIFEST)? {
+            Some(s) => Ok(Some(jar_manifest::parse(&s)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// The `META-INF/MANIFEST.MF` attributes read by Forge.
+    pub fn forge_attributes(&mut self) -> Result<ForgeAttributes> {
+        match self.jar_manifest()? {
+            Some(manifest) => Ok(ForgeAttributes::from_manifest(&manifest)?),
+            None => Ok(ForgeAttributes::default()),
+        }
+    }
+
+    /// The `Implementation-Version` in the `META-INF/MANIFEST.MF` of the jar.
+    pub fn implementation_version(&mut self) -> Result<Option<ForgeVersion>> {
+        let Some(s) = self.read_to_string(JAR_MANIFEST)? else {
+            return Ok(None);
+        };
+
+        match jar_manifest::extract_implementation_version(&s) {
+            Ok(version) => Ok(Some(version)),
+            Err(jar_manifest::Error::MissingImplementationVersion) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Find every loader the jar has metadata for,
+    /// metadata that cannot be read is recorded in [`Detected::errors`] and the other loaders are still detected.
+    pub fn detect(&mut self) -> Detected {
+        let results = [
+            self.fabric_manifest()
+                .map(|m| m.map(|m| LoaderManifest::Fabric(Box::new(m)))),
+            self.quilt_manifest().map(|m| m.map(LoaderManifest::Quilt)),
+            self.forge_manifest().map(|m| m.map(LoaderManifest::Forge)),
+            self.neoforge_manifest()
+                .map(|m| m.map(LoaderManifest::NeoForge)),
+            self.mcmod_info()
+                .map(|m| m.map(LoaderManifest::LegacyForge)),
+        ];
+
+        let mut loaders = vec![];
+        let mut errors = vec![];
+        for result in results {
+            match result {
+                Ok(Some(manifest)) => loaders.push(manifest),
+                Ok(None) => {}
+                Err(e) => errors.push(e),
+            }
+        }
+
+        let attributes = self.forge_attributes().unwrap_or_else(|e| {
+            errors.push(e);
+            ForgeAttributes::default()
+        });
+        Detected {
+            library: (loaders.is_empty() && errors.is_empty()) || attributes.is_library(),
+            loaders,
+            errors,
+            attributes,
+        }
+    }
+
+    /// Iterate over the names of every file in the jar.
+    pub fn file_names(&self) -> impl Iterator<Item = &str> {
+        self.archive.file_names()
+    }
+}
+
+/// The loaders a jar was built for, see [`Jar::detect`].
+#[derive(Debug)]
+pub struct Detected {
+    pub loaders: Vec<LoaderManifest>,
+    /// Metadata files that exist but could not be read.
+    pub errors: Vec<Error>,
+    /// The jar has no mod metadata or is marked as a library with `FMLModType`.
+    pub library: bool,
+    pub attributes: ForgeAttributes,
+}
+
+impl Detected {
+    pub fn supports(&self, loader: Loader) -> bool {
+        self.loaders
+            .iter()
+            .any(|manifest| manifest.loader() == loader)
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum Loader {
+    Fabric,
+    Quilt,
+    Forge,
+    NeoForge,
+    /// Forge before 1.13, which used `mcmod.info`.
+    LegacyForge,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum LoaderManifest {
+    Fabric(Box<FabricManifest>),
+    Quilt(QuiltManifest),
+    Forge(UnsubstitutedForgeManifest),
+    NeoForge(UnsubstitutedForgeManifest),
+    LegacyForge(Vec<McModInfo>),
+}
+
+impl LoaderManifest {
+    pub fn loader(&self) -> Loader {
+        match self {
+            LoaderManifest::Fabric(_) => Loader::Fabric,
+            LoaderManifest::Quilt(_) => Loader::Quilt,
+            LoaderManifest::Forge(_) => Loader::Forge,
+            LoaderManifest::NeoForge(_) => Loader::NeoForge,
+            LoaderManifest::LegacyForge(_) => Loader::LegacyForge,
+        }
+    }
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    #[error(transparent)]
+    Zip(#[from] ZipError),
+    #[error("file is not valid UTF-8: {0}")]
+    Utf8(#[from] std::string::FromUtf8Error),
+    #[error("{FABRIC_MANIFEST}: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("{QUILT_MANIFEST}: {0}")]
+    Quilt(serde_json::Error),
+    #[error("{FORGE_MANIFEST}: {0}")]
+    Toml(#[from] toml::de::Error),
+    #[error("{NEOFORGE_MANIFEST}: {0}")]
+    NeoForge(toml::de::Error),
+    #[error("{MCMOD_INFO}: {0}")]
+    McModInfo(serde_json::Error),
+    #[error("{JAR_MANIFEST}: {0}")]
+    JarManifest(#[from] jar_manifest::Error),
+    #[error("{FORGE_MANIFEST}: {0}")]
+    Forge(#[from] forge::Error),
+}
+
+#[cfg(test)]
+mod test {
+    use std::io::{Cursor, Write};
+
+    use zip::{ZipWriter, write::SimpleFileOptions};
+
+    use super::*;
+
+    /// Build an in-memory jar from a list of file names and contents.
+    fn jar(files: &[(&str, &str)]) -> Jar<Cursor<Vec<u8>>> {
+        let mut writer = ZipWriter::new(Cursor::new(vec![]));
+        for (name, contents) in files {
+            writer
+                .start_file(*name, SimpleFileOptions::default())
+                .unwrap();
+            writer.write_all(contents.as_bytes()).unwrap();
+        }
+        Jar::new(writer.finish().unwrap()).unwrap()
+    }
+
+    #[test]
+    fn read_manifests() {
+        let mut jar = jar(&[
+            (
+                FABRIC_MANIFEST,
+                r#"{ "schemaVersion": 1, "id": "examplemod", "version": "1.0.0" }"#,
+            ),
+            (
+                FORGE_MANIFEST,
+                r#"
+                modLoader="javafml"
+                loaderVersion="[41,)"
+                license="MIT"
+                [[mods]]
+                modId="examplemod"
+                "#,
+            ),
+        ]);
+
+        let fabric = jar.fabric_manifest().unwrap().unwrap();
+        assert_eq!(fabric.id.0, "examplemod");
+        let forge = jar.forge_manifest().unwrap().unwrap();
+        assert_eq!(forge.mods[0].mod_id.0, "examplemod");
+        assert!(jar.read(JAR_MANIFEST).unwrap().is_none());
+    }
+
+    #[test]
+    fn detect_loaders() {
+        let mut multi = jar(&[
+            (
+                FABRIC_MANIFEST,
+                r#"{ "schemaVersion": 1, "id": "examplemod", "version": "1.0.0" }"#,
+            ),
+            (
+                NEOFORGE_MANIFEST,
+                r#"
+                modLoader="javafml"
+                loaderVersion="[1,)"
+                license="MIT"
+                [[mods]]
+                modId="examplemod"
+                [[dependencies.examplemod]]
+                modId="neoforge"
+                type="required"
+                versionRange="[20.4,)"
+                "#,
+            ),
+        ]);
+        let detected = multi.detect();
+        let loaders: Vec<Loader> = detected.loaders.iter().map(|m| m.loader()).collect();
+        assert_eq!(loaders, vec![Loader::Fabric, Loader::NeoForge]);
+        assert!(detected.supports(Loader::NeoForge));
+        assert!(!detected.library);
+
+        let detected = jar(&[("com/example/Lib.class", "")]).detect();
+        assert!(detected.loaders.is_empty());
+        assert!(detected.library);
+
+        let detected = jar(&[
+            (JAR_MANIFEST, "Manifest-Version: 1.0\nFMLModType: LIBRARY\n"),
+            (MCMOD_INFO, r#"[{ "modid": "examplemod" }]"#),
+        ])
+        .detect();
+        assert!(detected.supports(Loader::LegacyForge));
+        assert!(detected.library);
+
+        let detected = jar(&[
+            (JAR_MANIFEST, "Manifest-Version: 1.0\nFMLModType: PLUGIN\n"),
+            (
+                FABRIC_MANIFEST,
+                r#"{ "schemaVersion": 1, "id": "examplemod", "version": "1.0.0" }"#,
+            ),
+            (QUILT_MANIFEST, "{"),
+            (NEOFORGE_MANIFEST, "modLoader="),
+        ])
+        .detect();
+        let loaders: Vec<Loader> = detected.loaders.iter().map(|m| m.loader()).collect();
+        assert_eq!(loaders, vec![Loader::Fabric]);
+        assert!(matches!(
+            detected.errors[..],
+            [Error::Quilt(_), Error::NeoForge(_), Error::JarManifest(_)]
+        ));
+        assert!(!detected.library);
+    }
+}
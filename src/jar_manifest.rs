@@ -1,30 +1,411 @@
+use std::fmt;
+
 use crate::forge::{self, version::ForgeVersion};
 
-pub fn parse(s: &str) -> impl Iterator<Item = Result<(&str, &str), Error>> {
-    let iter = s.lines();
+/// Lines in a manifest may not be longer than this many bytes, excluding the line break.
+const MAX_LINE_LEN: usize = 72;
+
+/// A `META-INF/MANIFEST.MF` file.
+#[derive(PartialEq, Eq, Debug, Clone, Default)]
+pub struct JarManifest {
+    /// Attributes of the main section, which apply to the whole jar.
+    pub main: Attributes,
+    /// Per-entry sections, each starting with a `Name` attribute.
+    pub sections: Vec<Section>,
+}
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Section {
+    pub name: String,
+    pub attributes: Attributes,
+}
+
+/// Attributes in the order they appear in the manifest.
+/// Names are compared case-insensitively.
+#[derive(PartialEq, Eq, Debug, Clone, Default)]
+pub struct Attributes(Vec<(String, String)>);
+
+impl Attributes {
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.0
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// Set an attribute, replacing the value if it is already present.
+    pub fn insert(&mut self, name: impl Into<String>, value: impl Into<String>) {
+        let name = name.into();
+        let value = value.into();
+        match self
+            .0
+            .iter_mut()
+            .find(|(k, _)| k.eq_ignore_ascii_case(&name))
+        {
+            Some((_, v)) => *v = value,
+            None => self.0.push((name, value)),
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.0.iter().map(|(k, v)| (k.as_str(), v.as_str()))
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl JarManifest {
+    /// Get the per-entry section for a path in the jar.
+    pub fn section(&self, name: &str) -> Option<&Attributes> {
+        self.sections
+            .iter()
+            .find(|section| section.name == name)
+            .map(|section| &section.attributes)
+    }
+}
+
+/// Attributes in the main section of a jar manifest that Forge and NeoForge read.
+#[derive(PartialEq, Eq, Debug, Clone, Default)]
+pub struct ForgeAttributes {
+    /// `FMLModType`, mods are assumed when missing.
+    pub mod_type: Option<ModType>,
+    /// `MixinConfigs`, a comma separated list of mixin config files.
+    pub mixin_configs: Vec<String>,
+    pub automatic_module_name: Option<String>,
+    /// `FMLCorePlugin`, the class name of a legacy coremod.
+    pub core_plugin: Option<String>,
+    /// `FMLCorePluginContainsFMLMod`, if a coremod jar also contains a regular mod.
+    pub core_plugin_contains_fml_mod: bool,
+    /// `TweakClass`, a LaunchWrapper tweaker.
+    pub tweak_class: Option<String>,
+    pub specification_version: Option<String>,
+}
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum ModType {
+    Mod,
+    Library,
+    GameLibrary,
+    LangProvider,
+}
+
+impl ModType {
+    pub fn parse(s: &str) -> Result<Self, Error> {
+        match s {
+            "MOD" => Ok(ModType::Mod),
+            "LIBRARY" => Ok(ModType::Library),
+            "GAMELIBRARY" => Ok(ModType::GameLibrary),
+            "LANGPROVIDER" => Ok(ModType::LangProvider),
+            _ => Err(Error::InvalidModType(s.to_string())),
+        }
+    }
+}
+
+impl ForgeAttributes {
+    pub fn from_manifest(manifest: &JarManifest) -> Result<Self, Error> {
+        let main = &manifest.main;
+        let string = |name| main.get(name).map(|value| value.trim().to_string());
+
+        Ok(Self {
+            mod_type: main
+                .get("FMLModType")
+                .map(|value| ModType::parse(value.trim()))
+                .transpose()?,
+            mixin_configs: main
+                .get("MixinConfigs")
+                .into_iter()
+                .flat_map(|value| value.split(','))
+                .map(str::trim)
+                .filter(|config| !config.is_empty())
+                .map(String::from)
+                .collect(),
+            automatic_module_name: string("Automatic-Module-Name"),
+            core_plugin: string("FMLCorePlugin"),
+            core_plugin_contains_fml_mod: main
+                .get("FMLCorePluginContainsFMLMod")
+                .is_some_and(|value| value.trim().eq_ignore_ascii_case("true")),
+            tweak_class: string("TweakClass"),
+            specification_version: string("Specification-Version"),
+        })
+    }
+
+    /// Libraries and language providers are loaded by Forge but are not mods themselves.
+    pub fn is_library(&self) -> bool {
+        matches!(
+            self.mod_type,
+            Some(ModType::Library | ModType::GameLibrary | ModType::LangProvider)
+        )
+    }
+
+    /// Legacy coremods hook into the game through `FMLCorePlugin` or a LaunchWrapper tweaker.
+    pub fn is_coremod(&self) -> bool {
+        self.core_plugin.is_some() || self.tweak_class.is_some()
+    }
+}
+
+pub fn parse(s: &str) -> Result<JarManifest, Error> {
+    let mut blocks = vec![];
+    let mut block: Vec<(String, String)> = vec![];
+
+    for (i, line) in lines(s).enumerate() {
+        let line_number = i + 1;
+
+        if line.is_empty() {
+            if !block.is_empty() {
+                blocks.push(std::mem::take(&mut block));
+            }
+        } else if let Some(continuation) = line.strip_prefix(' ') {
+            match block.last_mut() {
+                Some((_, value)) => value.push_str(continuation),
+                None => return Err(Error::UnexpectedContinuation(line_number)),
+            }
+        } else {
+            let (key, value) = line
+                .split_once(':')
+                .ok_or(Error::MissingDelimeter(line_number))?;
+            let value = value.strip_prefix(' ').unwrap_or(value);
+            block.push((key.to_string(), value.to_string()));
+        }
+    }
+    if !block.is_empty() {
+        blocks.push(block);
+    }
+
+    let mut blocks = blocks.into_iter();
+    let mut manifest = JarManifest::default();
+
+    // The main section is optional, a manifest may start directly with a `Name` section.
+    let mut next = blocks.next();
+    if let Some(block) = &next
+        && !block[0].0.eq_ignore_ascii_case("Name")
+    {
+        manifest.main = Attributes(block.clone());
+        next = blocks.next();
+    }
+
+    while let Some(mut block) = next {
+        let (key, name) = block.remove(0);
+        if !key.eq_ignore_ascii_case("Name") {
+            return Err(Error::MissingSectionName(key));
+        }
+        manifest.sections.push(Section {
+            name,
+            attributes: Attributes(block),
+        });
+        next = blocks.next();
+    }
+
+    Ok(manifest)
+}
 
-    iter.map(|line| match line.split_once(':') {
-        Some((k, v)) => Ok((k.trim(), v.trim())),
-        None => Err(Error::MissingDelimeter),
+/// Split on `\r\n`, `\n` or `\r`.
+fn lines(s: &str) -> impl Iterator<Item = &str> {
+    let mut rest = s;
+    std::iter::from_fn(move || {
+        if rest.is_empty() {
+            return None;
+        }
+        let end = rest.find(['\r', '\n']).unwrap_or(rest.len());
+        let line = &rest[..end];
+        rest = &rest[end..];
+        rest = rest
+            .strip_prefix("\r\n")
+            .or_else(|| rest.strip_prefix(['\r', '\n']))
+            .unwrap_or(rest);
+        Some(line)
     })
 }
 
-pub fn extract_implementation_version(s: &str) -> Result<ForgeVersion, Error> {
-    for line in parse(s) {
-        let (key, value) = line?;
-        if key == "Implementation-Version" {
-            return Ok(ForgeVersion::parse(value)?);
+/// Write a header, wrapping it onto continuation lines so no line is longer than 72 bytes.
+fn write_header(f: &mut fmt::Formatter<'_>, key: &str, value: &str) -> fmt::Result {
+    let line = format!("{key}: {value}");
+    let mut rest = line.as_str();
+    let mut limit = MAX_LINE_LEN;
+
+    loop {
+        if rest.len() <= limit {
+            return write!(f, "{rest}\r\n");
+        }
+
+        // Never split a multi-byte character across lines.
+        let mut end = limit;
+        while !rest.is_char_boundary(end) {
+            end -= 1;
+        }
+        write!(f, "{}\r\n ", &rest[..end])?;
+        rest = &rest[end..];
+        limit = MAX_LINE_LEN - 1;
+    }
+}
+
+/// Writes the manifest with CRLF line breaks as the `jar` tool does.
+impl fmt::Display for JarManifest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (key, value) in self.main.iter() {
+            write_header(f, key, value)?;
         }
+        write!(f, "\r\n")?;
+
+        for section in &self.sections {
+            write_header(f, "Name", &section.name)?;
+            for (key, value) in section.attributes.iter() {
+                write_header(f, key, value)?;
+            }
+            write!(f, "\r\n")?;
+        }
+
+        Ok(())
+    }
+}
+
+pub fn extract_implementation_version(s: &str) -> Result<ForgeVersion, Error> {
+    let manifest = parse(s)?;
+    match manifest.main.get("Implementation-Version") {
+        Some(version) => Ok(ForgeVersion::parse(version)?),
+        None => Err(Error::MissingImplementationVersion),
     }
-    Err(Error::MissingImplementationVersion)
 }
 
 #[derive(thiserror::Error, Debug)]
 pub enum Error {
-    #[error("missing ':' property delimeter")]
-    MissingDelimeter,
+    #[error("line {0}: missing ':' property delimeter")]
+    MissingDelimeter(usize),
+    #[error("line {0}: continuation line without a preceding header")]
+    UnexpectedContinuation(usize),
+    #[error("section starts with {0} instead of Name")]
+    MissingSectionName(String),
+    #[error("invalid FMLModType {0}")]
+    InvalidModType(String),
     #[error("META-INF/MANIFEST.MF missing Implementation-Version")]
     MissingImplementationVersion,
     #[error("Implementation-Version: {0}")]
     Forge(#[from] forge::version::Error),
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_manifest() {
+        let s = "Manifest-Version: 1.0\r\n\
+            Created-By: 17.0.8 (Eclipse Adoptium)\r\n\
+            MixinConfigs: examplemod.mixins.json,examplemod.client.mixins.json,exam\r\n \
+            plemod.compat.mixins.json\r\n\
+            implementation-version: 1.2.3\r\n\
+            \r\n\
+            Name: net/example/Foo.class\r\n\
+            SHA-256-Digest: abc=\r\n\
+            \r\n\
+            Name: net/example/very/long/package/name/that/does/not/fit/on/one/li\r\n \
+            ne/Bar.class\r\n\
+            SHA-256-Digest: def=\r\n\
+            \r\n";
+
+        let manifest = parse(s).unwrap();
+        assert_eq!(manifest.main.len(), 4);
+        assert_eq!(
+            manifest.main.get("MixinConfigs"),
+            Some(
+                "examplemod.mixins.json,examplemod.client.mixins.json,examplemod.compat.mixins.json"
+            )
+        );
+        assert_eq!(manifest.main.get("Implementation-Version"), Some("1.2.3"));
+        assert_eq!(manifest.sections.len(), 2);
+        assert_eq!(
+            manifest
+                .section("net/example/Foo.class")
+                .unwrap()
+                .get("sha-256-digest"),
+            Some("abc=")
+        );
+        assert_eq!(
+            manifest.sections[1].name,
+            "net/example/very/long/package/name/that/does/not/fit/on/one/line/Bar.class"
+        );
+
+        assert_eq!(
+            extract_implementation_version(s).unwrap(),
+            ForgeVersion::parse("1.2.3").unwrap()
+        );
+        assert!(matches!(
+            parse("Manifest-Version: 1.0\n\nSHA-256-Digest: abc=\n"),
+            Err(Error::MissingSectionName(_))
+        ));
+        assert!(matches!(
+            parse(" 1.0\n"),
+            Err(Error::UnexpectedContinuation(1))
+        ));
+        assert!(matches!(
+            parse("Manifest-Version: 1.0\nfoo\n"),
+            Err(Error::MissingDelimeter(2))
+        ));
+    }
+
+    #[test]
+    fn write_manifest() {
+        let mut manifest = JarManifest::default();
+        manifest.main.insert("Manifest-Version", "1.0");
+        manifest.main.insert("Class-Path", "lib/é".repeat(40));
+        manifest.main.insert("manifest-version", "2.0");
+        manifest.sections.push(Section {
+            name: "a/b.class".into(),
+            attributes: Attributes(vec![("SHA-256-Digest".into(), "abc=".into())]),
+        });
+
+        let s = manifest.to_string();
+        assert!(s.starts_with("Manifest-Version: 2.0\r\nClass-Path: lib/é"));
+        assert!(s.ends_with("\r\n\r\nName: a/b.class\r\nSHA-256-Digest: abc=\r\n\r\n"));
+        assert!(s.split("\r\n").all(|line| line.len() <= MAX_LINE_LEN));
+        assert_eq!(parse(&s).unwrap(), manifest);
+    }
+
+    #[test]
+    fn forge_attributes() {
+        let manifest = parse(
+            "Manifest-Version: 1.0\n\
+            FMLModType: GAMELIBRARY\n\
+            MixinConfigs: a.mixins.json, b.mixins.json\n\
+            Automatic-Module-Name: com.example.lib\n\
+            Specification-Version: 1\n",
+        )
+        .unwrap();
+        let attributes = ForgeAttributes::from_manifest(&manifest).unwrap();
+        assert_eq!(
+            attributes,
+            ForgeAttributes {
+                mod_type: Some(ModType::GameLibrary),
+                mixin_configs: vec!["a.mixins.json".into(), "b.mixins.json".into()],
+                automatic_module_name: Some("com.example.lib".into()),
+                core_plugin: None,
+                core_plugin_contains_fml_mod: false,
+                tweak_class: None,
+                specification_version: Some("1".into()),
+            }
+        );
+        assert!(attributes.is_library());
+        assert!(!attributes.is_coremod());
+
+        let manifest = parse(
+            "FMLCorePlugin: com.example.asm.LoadingPlugin\n\
+            FMLCorePluginContainsFMLMod: True\n",
+        )
+        .unwrap();
+        let attributes = ForgeAttributes::from_manifest(&manifest).unwrap();
+        assert!(attributes.is_coremod());
+        assert!(attributes.core_plugin_contains_fml_mod);
+        assert!(!attributes.is_library());
+
+        let manifest = parse("FMLModType: PLUGIN\n").unwrap();
+        assert!(matches!(
+            ForgeAttributes::from_manifest(&manifest),
+            Err(Error::InvalidModType(_))
+        ));
+    }
+}
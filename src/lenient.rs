@@ -0,0 +1,49 @@
+use std::fmt;
+
+use crate::located::Located;
+
+/// Input a loader accepts that strict parsing rejects, which lenient parsing normalized.
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum Warning {
+    /// Fabric skips the empty terms between consecutive spaces, e.g. `>=1.0  <2.0`.
+    RepeatedSpace,
+    /// Fabric reads an empty or blank predicate as `*`.
+    EmptyPredicate,
+    /// Fabric skips `*` when it is next to other terms, e.g. `* >=1.0`.
+    IgnoredWildcard,
+    /// A `v` before a version, e.g. `v1.2.3`.
+    LeadingV,
+    /// Maven does not need a `,` between sets, e.g. `[1,2)[3,)`.
+    MissingComma,
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Warning::RepeatedSpace => write!(f, "repeated space between terms"),
+            Warning::EmptyPredicate => write!(f, "empty predicate matches any version"),
+            Warning::IgnoredWildcard => write!(f, "`*` next to other terms is ignored"),
+            Warning::LeadingV => write!(f, "leading `v` before the version"),
+            Warning::MissingComma => write!(f, "missing ',' between sets"),
+        }
+    }
+}
+
+/// A warning for a value in a manifest, `field` is a path such as `depends.sodium`.
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct ManifestWarning {
+    pub field: String,
+    pub warning: Located<Warning>,
+}
+
+impl fmt::Display for ManifestWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.field, self.warning)
+    }
+}
+
+/// Strip a `v` directly followed by a digit.
+pub(crate) fn strip_v(s: &str) -> Option<&str> {
+    s.strip_prefix(['v', 'V'])
+        .filter(|rest| rest.starts_with(|ch: char| ch.is_ascii_digit()))
+}
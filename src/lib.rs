@@ -1,7 +1,16 @@
 #![warn(clippy::cargo)]
 
+pub mod compat;
+pub mod convert;
 pub mod curseforge;
 pub mod fabric;
 pub mod forge;
+pub mod jar;
 pub mod jar_manifest;
+pub mod lenient;
+pub mod lint;
+pub mod located;
+pub mod metadata;
 pub mod modrinth;
+pub mod pack;
+pub mod quilt;
@@ -0,0 +1,223 @@
+use std::{fmt, ops::Range};
+
+use serde::Serialize;
+
+pub mod fabric;
+pub mod forge;
+mod json;
+
+/// A problem found in a manifest file.
+#[derive(Serialize, PartialEq, Eq, Debug, Clone)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub span: Span,
+    pub message: String,
+}
+
+#[derive(Serialize, PartialEq, Eq, Debug, Clone, Copy, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum Severity {
+    Warning,
+    Error,
+}
+
+/// A location in a source file.
+/// Lines and columns start at 1, columns are counted in characters.
+#[derive(Serialize, PartialEq, Eq, Debug, Clone, Copy)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+    pub line: usize,
+    pub column: usize,
+}
+
+impl Span {
+    pub fn new(source: &str, range: Range<usize>) -> Self {
+        let start = range.start.min(source.len());
+        let before = &source[..start];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+
+        Self {
+            start,
+            end: range.end.clamp(start, source.len()),
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+        }
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let severity = match self.severity {
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        };
+        write!(
+            f,
+            "{}:{}: {severity}: {}",
+            self.span.line, self.span.column, self.message
+        )
+    }
+}
+
+/// A value in a JSON or TOML document with the byte range it was parsed from.
+#[derive(PartialEq, Debug, Clone)]
+struct Node {
+    span: Range<usize>,
+    value: Value,
+}
+
+#[derive(PartialEq, Debug, Clone)]
+enum Value {
+    Null,
+    Bool(bool),
+    /// A number, integer or float. The original text is kept.
+    Number(String),
+    String(String),
+    /// A TOML date, time or datetime.
+    Datetime(String),
+    Array(Vec<Node>),
+    Object(Vec<(Key, Node)>),
+}
+
+#[derive(PartialEq, Debug, Clone)]
+struct Key {
+    span: Range<usize>,
+    name: String,
+}
+
+impl Node {
+    fn as_str(&self) -> Option<&str> {
+        match &self.value {
+            Value::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    fn as_object(&self) -> Option<&[(Key, Node)]> {
+        match &self.value {
+            Value::Object(entries) => Some(entries),
+            _ => None,
+        }
+    }
+
+    fn as_array(&self) -> Option<&[Node]> {
+        match &self.value {
+            Value::Array(items) => Some(items),
+            _ => None,
+        }
+    }
+
+    fn get(&self, key: &str) -> Option<&Node> {
+        self.as_object()?
+            .iter()
+            .find(|(k, _)| k.name == key)
+            .map(|(_, value)| value)
+    }
+
+    fn type_name(&self) -> &'static str {
+        match self.value {
+            Value::Null => "null",
+            Value::Bool(_) => "boolean",
+            Value::Number(_) => "number",
+            Value::String(_) => "string",
+            Value::Datetime(_) => "datetime",
+            Value::Array(_) => "array",
+            Value::Object(_) => "object",
+        }
+    }
+}
+
+/// Collects diagnostics for a single source file.
+struct Linter<'a> {
+    source: &'a str,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl<'a> Linter<'a> {
+    fn new(source: &'a str) -> Self {
+        Self {
+            source,
+            diagnostics: vec![],
+        }
+    }
+
+    fn push(&mut self, severity: Severity, span: Range<usize>, message: impl Into<String>) {
+        self.diagnostics.push(Diagnostic {
+            severity,
+            span: Span::new(self.source, span),
+            message: message.into(),
+        });
+    }
+
+    fn error(&mut self, span: Range<usize>, message: impl Into<String>) {
+        self.push(Severity::Error, span, message);
+    }
+
+    fn warning(&mut self, span: Range<usize>, message: impl Into<String>) {
+        self.push(Severity::Warning, span, message);
+    }
+
+    /// Warn about every key in an object that is not in `known`.
+    fn unknown_keys(&mut self, node: &Node, known: &[&str], context: &str) {
+        for (key, _) in node.as_object().unwrap_or_default() {
+            if !known.contains(&key.name.as_str()) {
+                self.warning(
+                    key.span.clone(),
+                    format!("unknown key `{}` in {context}", key.name),
+                );
+            }
+        }
+    }
+
+    /// Get a required string from an object, reporting an error if it is missing or not a string.
+    fn required_str<'n>(&mut self, node: &'n Node, key: &str) -> Option<(&'n str, Range<usize>)> {
+        match node.get(key) {
+            Some(value) => self.expect_str(value, key),
+            None => {
+                self.error(node.span.clone(), format!("missing required key `{key}`"));
+                None
+            }
+        }
+    }
+
+    fn expect_str<'n>(&mut self, value: &'n Node, key: &str) -> Option<(&'n str, Range<usize>)> {
+        match value.as_str() {
+            Some(s) => Some((s, value.span.clone())),
+            None => {
+                self.error(
+                    value.span.clone(),
+                    format!("`{key}` must be a string, found {}", value.type_name()),
+                );
+                None
+            }
+        }
+    }
+
+    fn finish(mut self) -> Vec<Diagnostic> {
+        self.diagnostics.sort_by_key(|d| d.span.start);
+        self.diagnostics
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn span_line_column() {
+        let source = "ab\ncdé\nf";
+        assert_eq!(
+            Span::new(source, 3..4),
+            Span {
+                start: 3,
+                end: 4,
+                line: 2,
+                column: 1
+            }
+        );
+        assert_eq!(Span::new(source, 8..9).column, 1);
+        assert_eq!(Span::new(source, 7..8).column, 4);
+        assert_eq!(Span::new(source, 100..200).end, source.len());
+    }
+}
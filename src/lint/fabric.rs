@@ -0,0 +1,231 @@
+use std::ops::Range;
+
+use super::{Diagnostic, Linter, Node, Value, json};
+use crate::fabric::{
+    ModId,
+    version::{FabricVersion, FabricVersionPredicate, FabricVersionRange, SemVer},
+};
+
+const KEYS: &[&str] = &[
+    "schemaVersion",
+    "id",
+    "version",
+    "provides",
+    "environment",
+    "entrypoints",
+    "jars",
+    "languageAdapters",
+    "mixins",
+    "accessWidener",
+    "depends",
+    "recommends",
+    "suggests",
+    "breaks",
+    "conflicts",
+    "name",
+    "description",
+    "contact",
+    "authors",
+    "contributors",
+    "license",
+    "icon",
+    "custom",
+];
+
+/// Lint the source of a `fabric.mod.json` file.
+pub fn lint(source: &str) -> Vec<Diagnostic> {
+    let mut linter = Linter::new(source);
+
+    let root = match json::parse(source) {
+        Ok(root) => root,
+        Err((message, span)) => {
+            linter.error(span, message);
+            return linter.finish();
+        }
+    };
+
+    if root.as_object().is_none() {
+        linter.error(root.span.clone(), "fabric.mod.json must be an object");
+        return linter.finish();
+    }
+
+    linter.unknown_keys(&root, KEYS, "fabric.mod.json");
+
+    match root.get("schemaVersion") {
+        Some(Node {
+            value: Value::Number(n),
+            ..
+        }) if n == "1" => {}
+        Some(node) => linter.error(node.span.clone(), "`schemaVersion` must be 1"),
+        None => linter.error(0..0, "missing required key `schemaVersion`"),
+    }
+
+    let id = linter.required_str(&root, "id");
+    if let Some((id, span)) = &id {
+        check_id(&mut linter, id, span.clone());
+    }
+
+    if let Some((version, span)) = linter.required_str(&root, "version") {
+        match FabricVersion::parse(version, false) {
+            Ok(_) if SemVer::parse(version, false).is_err() => linter.warning(
+                span,
+                format!(
+                    "version `{version}` is not a semantic version and will be compared as a string"
+                ),
+            ),
+            Ok(_) => {}
+            Err(e) => linter.error(span, format!("invalid version `{version}`: {e}")),
+        }
+    }
+
+    if let Some(provides) = root.get("provides") {
+        match provides.as_array() {
+            Some(items) => {
+                for item in items {
+                    if let Some((provided, span)) = linter.expect_str(item, "provides") {
+                        check_id(&mut linter, provided, span);
+                    }
+                }
+            }
+            None => linter.error(provides.span.clone(), "`provides` must be an array"),
+        }
+    }
+
+    if let Some(environment) = root.get("environment")
+        && let Some((value, span)) = linter.expect_str(environment, "environment")
+        && !["client", "server", "*"].contains(&value)
+    {
+        linter.error(
+            span,
+            format!("invalid environment `{value}`, expected `client`, `server` or `*`"),
+        );
+    }
+
+    let own_id = id.map(|(id, _)| id);
+    for key in ["depends", "recommends", "suggests", "breaks", "conflicts"] {
+        if let Some(deps) = root.get(key) {
+            check_dependencies(&mut linter, key, deps, own_id);
+        }
+    }
+
+    linter.finish()
+}
+
+fn check_dependencies(linter: &mut Linter, key: &str, deps: &Node, own_id: Option<&str>) {
+    let Some(entries) = deps.as_object() else {
+        linter.error(deps.span.clone(), format!("`{key}` must be an object"));
+        return;
+    };
+
+    for (dep, value) in entries {
+        // Fabric Loader accepts any dependency key, an invalid one can only never match.
+        if let Err(e) = ModId::validate(&dep.name) {
+            linter.warning(
+                dep.span.clone(),
+                format!("invalid mod id `{}`: {e}", dep.name),
+            );
+        }
+
+        if own_id == Some(dep.name.as_str()) {
+            linter.warning(
+                dep.span.clone(),
+                format!("mod `{}` declares itself in `{key}`", dep.name),
+            );
+        }
+
+        let predicates: Vec<&Node> = match &value.value {
+            Value::String(_) => vec![value],
+            Value::Array(items) => items.iter().collect(),
+            _ => {
+                linter.error(
+                    value.span.clone(),
+                    "version range must be a string or an array of strings",
+                );
+                continue;
+            }
+        };
+
+        let mut parsed = vec![];
+        for predicate in predicates {
+            let Some((s, span)) = linter.expect_str(predicate, key) else {
+                continue;
+            };
+            match FabricVersionPredicate::parse(s) {
+                Ok(_) => parsed.push(s),
+                Err(e) => linter.error(span, format!("invalid version range `{s}`: {e}")),
+            }
+        }
+
+        if let Ok(range) = FabricVersionRange::parse_many(parsed.iter())
+            && !range.is_satisfiable()
+        {
+            let message = format!("version range for `{}` can never match", dep.name);
+            if key == "depends" {
+                linter.error(value.span.clone(), message);
+            } else {
+                linter.warning(value.span.clone(), message);
+            }
+        }
+    }
+}
+
+fn check_id(linter: &mut Linter, id: &str, span: Range<usize>) {
+    if let Err(e) = ModId::validate(id) {
+        linter.error(span, format!("invalid mod id `{id}`: {e}"));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::lint::Severity;
+
+    #[test]
+    fn lint_mod_json() {
+        let source = r#"{
+    "schemaVersion": 1,
+    "id": "Example",
+    "version": "1.0.0",
+    "enviroment": "client",
+    "depends": {
+        "Example": "*",
+        "sodium": ">=2 <1",
+        "minecraft": ["1.20.1", ">=1.x.1"]
+    }
+}"#;
+
+        let diagnostics: Vec<String> = lint(source).iter().map(ToString::to_string).collect();
+        assert_eq!(
+            diagnostics,
+            vec![
+                "3:11: error: invalid mod id `Example`: mod id must start with a lowercase letter, found `E`",
+                "5:5: warning: unknown key `enviroment` in fabric.mod.json",
+                "7:9: warning: invalid mod id `Example`: mod id must start with a lowercase letter, found `E`",
+                "7:9: warning: mod `Example` declares itself in `depends`",
+                "8:19: error: version range for `sodium` can never match",
+                "9:33: error: invalid version range `>=1.x.1`: interjacent wildcards (e.g., 1.x.2) are disallowed at `>=1.x.1` (offset 0)",
+            ]
+        );
+    }
+
+    #[test]
+    fn lint_syntax_error() {
+        let diagnostics = lint("{\n  \"id\": \"abc\",\n}");
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].severity, Severity::Error);
+        assert_eq!(diagnostics[0].span.line, 3);
+        assert_eq!(diagnostics[0].span.column, 1);
+    }
+
+    #[test]
+    fn lint_valid() {
+        let source = r#"{
+            "schemaVersion": 1,
+            "id": "example-mod_1",
+            "version": "1.0.0+mc1.20.1",
+            "environment": "*",
+            "depends": { "fabricloader": ">=0.14", "minecraft": ["1.20", "1.20.1"] }
+        }"#;
+        assert_eq!(lint(source), vec![]);
+    }
+}
@@ -0,0 +1,448 @@
+use std::{collections::HashMap, ops::Range};
+
+use toml::{
+    Spanned,
+    de::{DeTable, DeValue},
+};
+
+use super::{Diagnostic, Key, Linter, Node, Span, Value};
+use crate::forge::{
+    ModId,
+    version::{ForgeVersion, ForgeVersionRange},
+};
+
+const KEYS: &[&str] = &[
+    "modLoader",
+    "loaderVersion",
+    "license",
+    "issueTrackerURL",
+    "showAsResourcePack",
+    "showAsDataPack",
+    "clientSideOnly",
+    "displayURL",
+    "logoFile",
+    "services",
+    "properties",
+    "mods",
+    "dependencies",
+    "modproperties",
+    "features",
+    "accessTransformers",
+    "mixins",
+];
+
+const MOD_KEYS: &[&str] = &[
+    "modId",
+    "namespace",
+    "version",
+    "displayName",
+    "description",
+    "logoFile",
+    "logoBlur",
+    "updateJSONURL",
+    "credits",
+    "authors",
+    "displayURL",
+    "displayTest",
+    "enumExtensions",
+    "featureFlags",
+];
+
+const DEPENDENCY_KEYS: &[&str] = &[
+    "modId",
+    "mandatory",
+    "type",
+    "versionRange",
+    "ordering",
+    "side",
+    "referralUrl",
+    "reason",
+];
+
+/// Lint the source of a `META-INF/mods.toml` file.
+pub fn lint(source: &str) -> Vec<Diagnostic> {
+    let mut linter = Linter::new(source);
+
+    let root = match DeTable::parse(source) {
+        Ok(root) => Node {
+            span: root.span(),
+            value: Value::Object(table(root.get_ref())),
+        },
+        Err(e) => {
+            linter.error(e.span().unwrap_or(0..0), e.message().trim());
+            return linter.finish();
+        }
+    };
+
+    linter.unknown_keys(&root, KEYS, "mods.toml");
+
+    linter.required_str(&root, "modLoader");
+    linter.required_str(&root, "license");
+    if let Some((range, span)) = linter.required_str(&root, "loaderVersion") {
+        check_range(&mut linter, range, span, true);
+    }
+
+    let mut mod_ids: HashMap<String, Range<usize>> = HashMap::new();
+
+    match root.get("mods") {
+        Some(mods) => match mods.as_array() {
+            Some(mods) => {
+                for m in mods {
+                    check_mod(&mut linter, m, &mut mod_ids);
+                }
+            }
+            None => linter.error(mods.span.clone(), "`mods` must be an array of tables"),
+        },
+        None => linter.error(0..0, "missing required key `mods`"),
+    }
+
+    if let Some(dependencies) = root.get("dependencies") {
+        match dependencies.as_object() {
+            Some(entries) => {
+                for (key, deps) in entries {
+                    check_dependencies(&mut linter, key, deps, &mod_ids);
+                }
+            }
+            None => linter.error(dependencies.span.clone(), "`dependencies` must be a table"),
+        }
+    }
+
+    if let Some(properties) = root.get("modproperties") {
+        match properties.as_object() {
+            Some(entries) => {
+                for (key, table) in entries {
+                    check_mod_properties(&mut linter, key, table, &mod_ids);
+                }
+            }
+            None => linter.error(properties.span.clone(), "`modproperties` must be a table"),
+        }
+    }
+
+    if let Some(features) = root.get("features") {
+        match features.as_object() {
+            Some(entries) => {
+                for (key, table) in entries {
+                    check_features(&mut linter, key, table, &mod_ids);
+                }
+            }
+            None => linter.error(features.span.clone(), "`features` must be a table"),
+        }
+    }
+
+    linter.finish()
+}
+
+fn check_mod(linter: &mut Linter, m: &Node, mod_ids: &mut HashMap<String, Range<usize>>) {
+    if m.as_object().is_none() {
+        linter.error(m.span.clone(), "`mods` must be an array of tables");
+        return;
+    }
+
+    linter.unknown_keys(m, MOD_KEYS, "[[mods]]");
+
+    if let Some((id, span)) = linter.required_str(m, "modId") {
+        check_id(linter, id, span.clone());
+
+        match mod_ids.get(id) {
+            Some(first) => {
+                let line = Span::new(linter.source, first.clone()).line;
+                linter.error(
+                    span,
+                    format!("duplicate mod id `{id}`, first declared on line {line}"),
+                );
+            }
+            None => {
+                mod_ids.insert(id.to_string(), span);
+            }
+        }
+    }
+
+    if let Some(version) = m.get("version")
+        && let Some((version, span)) = linter.expect_str(version, "version")
+        && version != "${file.jarVersion}"
+        && let Err(e) = ForgeVersion::parse(version)
+    {
+        linter.error(span, format!("invalid version `{version}`: {e}"));
+    }
+
+    check_enum(
+        linter,
+        m,
+        "displayTest",
+        &[
+            "MATCH_VERSION",
+            "IGNORE_SERVER_VERSION",
+            "IGNORE_ALL_VERSION",
+            "NONE",
+        ],
+    );
+}
+
+fn check_mod_properties(
+    linter: &mut Linter,
+    key: &Key,
+    table: &Node,
+    mod_ids: &HashMap<String, Range<usize>>,
+) {
+    if table.as_object().is_none() {
+        linter.error(
+            table.span.clone(),
+            format!("`modproperties.{}` must be a table", key.name),
+        );
+    } else if !mod_ids.contains_key(&key.name) {
+        linter.warning(
+            key.span.clone(),
+            format!(
+                "properties declared for `{}` which is not in `[[mods]]`",
+                key.name
+            ),
+        );
+    }
+}
+
+fn check_features(
+    linter: &mut Linter,
+    key: &Key,
+    table: &Node,
+    mod_ids: &HashMap<String, Range<usize>>,
+) {
+    let Some(features) = table.as_object() else {
+        linter.error(
+            table.span.clone(),
+            format!("`features.{}` must be a table", key.name),
+        );
+        return;
+    };
+
+    if !mod_ids.contains_key(&key.name) {
+        linter.warning(
+            key.span.clone(),
+            format!(
+                "features declared for `{}` which is not in `[[mods]]`",
+                key.name
+            ),
+        );
+    }
+
+    for (feature, range) in features {
+        if let Some((range, span)) = linter.expect_str(range, &feature.name) {
+            check_range(linter, range, span, true);
+        }
+    }
+}
+
+fn check_dependencies(
+    linter: &mut Linter,
+    key: &Key,
+    deps: &Node,
+    mod_ids: &HashMap<String, Range<usize>>,
+) {
+    if !mod_ids.contains_key(&key.name) {
+        linter.warning(
+            key.span.clone(),
+            format!(
+                "dependencies declared for `{}` which is not in `[[mods]]`",
+                key.name
+            ),
+        );
+    }
+
+    let Some(deps) = deps.as_array() else {
+        linter.error(
+            deps.span.clone(),
+            format!("`dependencies.{}` must be an array of tables", key.name),
+        );
+        return;
+    };
+
+    for dep in deps {
+        if dep.as_object().is_none() {
+            linter.error(
+                dep.span.clone(),
+                format!("`dependencies.{}` must be an array of tables", key.name),
+            );
+            continue;
+        }
+
+        linter.unknown_keys(dep, DEPENDENCY_KEYS, "dependency");
+
+        if let Some((id, span)) = linter.required_str(dep, "modId") {
+            // Forge only checks the ids of `[[mods]]`, a dependency id that could never
+            // match a mod still loads.
+            if let Err(e) = ModId::validate(id) {
+                linter.warning(span.clone(), format!("invalid mod id `{id}`: {e}"));
+            }
+            if id == key.name {
+                linter.warning(span, format!("mod `{id}` depends on itself"));
+            }
+        }
+
+        let mandatory = match dep.get("mandatory") {
+            Some(Node {
+                value: Value::Bool(mandatory),
+                ..
+            }) => *mandatory,
+            Some(node) => {
+                linter.error(node.span.clone(), "`mandatory` must be a boolean");
+                false
+            }
+            None => {
+                if dep.get("type").is_none() {
+                    linter.error(dep.span.clone(), "missing required key `mandatory`");
+                }
+                true
+            }
+        };
+        // NeoForge's `type` replaces `mandatory`, like `forge::Dependency::is_mandatory`.
+        let required = match dep.get("type") {
+            Some(dependency_type) => dependency_type.as_str() == Some("required"),
+            None => mandatory,
+        };
+
+        if let Some((range, span)) = linter.required_str(dep, "versionRange") {
+            check_range(linter, range, span, required);
+        }
+
+        check_enum(linter, dep, "ordering", &["NONE", "BEFORE", "AFTER"]);
+        check_enum(linter, dep, "side", &["BOTH", "CLIENT", "SERVER"]);
+    }
+}
+
+fn check_enum(linter: &mut Linter, node: &Node, name: &str, values: &[&str]) {
+    if let Some(value) = node.get(name)
+        && let Some((value, span)) = linter.expect_str(value, name)
+        && !values.contains(&value)
+    {
+        linter.error(
+            span,
+            format!(
+                "invalid {name} `{value}`, expected one of {}",
+                values.join(", ")
+            ),
+        );
+    }
+}
+
+fn check_range(linter: &mut Linter, range: &str, span: Range<usize>, required: bool) {
+    match ForgeVersionRange::parse(range) {
+        Ok(parsed) if !parsed.is_satisfiable() => {
+            let message = format!("version range `{range}` can never match");
+            if required {
+                linter.error(span, message);
+            } else {
+                linter.warning(span, message);
+            }
+        }
+        Ok(_) => {}
+        Err(e) => linter.error(span, format!("invalid version range `{range}`: {e}")),
+    }
+}
+
+fn check_id(linter: &mut Linter, id: &str, span: Range<usize>) {
+    if let Err(e) = ModId::validate(id) {
+        linter.error(span, format!("invalid mod id `{id}`: {e}"));
+    }
+}
+
+fn table(table: &DeTable) -> Vec<(Key, Node)> {
+    table
+        .iter()
+        .map(|(key, value)| {
+            let key = Key {
+                span: key.span(),
+                name: key.get_ref().to_string(),
+            };
+            (key, node(value))
+        })
+        .collect()
+}
+
+fn node(value: &Spanned<DeValue>) -> Node {
+    let node = match value.get_ref() {
+        DeValue::String(s) => Value::String(s.to_string()),
+        DeValue::Integer(n) => Value::Number(n.as_str().to_string()),
+        DeValue::Float(n) => Value::Number(n.as_str().to_string()),
+        DeValue::Boolean(b) => Value::Bool(*b),
+        DeValue::Datetime(datetime) => Value::Datetime(datetime.to_string()),
+        DeValue::Array(items) => Value::Array(items.iter().map(node).collect()),
+        DeValue::Table(entries) => Value::Object(table(entries)),
+    };
+
+    Node {
+        span: value.span(),
+        value: node,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn lint_mods_toml() {
+        let source = r#"modLoader="javafml"
+loaderVersion="[41,)"
+license="MIT"
+issueTrackerUrl="https://example.com"
+
+[[mods]]
+modId="examplemod"
+version="1.0.0"
+
+[[mods]]
+modId="examplemod"
+version="${file.jarVersion}"
+displayTest="IGNORE_CLIENT_VERSION"
+
+[[dependencies.examplemod]]
+modId="examplemod"
+mandatory=true
+versionRange="[2,1)"
+side="EVERYWHERE"
+
+[[dependencies.othermod]]
+modId="x"
+mandatory=false
+versionRange="[1,"
+
+[modproperties.examplemod]
+configScreen=true
+
+[modproperties.othermod]
+
+[features.examplemod]
+javaVersion="[21"
+
+[[dependencies.examplemod]]
+modId="jei"
+type="required"
+versionRange="[2,1)"
+"#;
+
+        let diagnostics: Vec<String> = lint(source).iter().map(ToString::to_string).collect();
+        assert_eq!(
+            diagnostics,
+            vec![
+                "4:1: warning: unknown key `issueTrackerUrl` in mods.toml",
+                "11:7: error: duplicate mod id `examplemod`, first declared on line 7",
+                "13:13: error: invalid displayTest `IGNORE_CLIENT_VERSION`, expected one of MATCH_VERSION, IGNORE_SERVER_VERSION, IGNORE_ALL_VERSION, NONE",
+                "16:7: warning: mod `examplemod` depends on itself",
+                "18:14: error: version range `[2,1)` can never match",
+                "19:6: error: invalid side `EVERYWHERE`, expected one of BOTH, CLIENT, SERVER",
+                "21:16: warning: dependencies declared for `othermod` which is not in `[[mods]]`",
+                "22:7: warning: invalid mod id `x`: mod id must be at least 2 characters",
+                "24:14: error: invalid version range `[1,`: expected ']' or ')' at end of input",
+                "29:16: warning: properties declared for `othermod` which is not in `[[mods]]`",
+                "32:13: error: invalid version range `[21`: expected ',' at end of input",
+                "37:14: error: version range `[2,1)` can never match",
+            ]
+        );
+    }
+
+    #[test]
+    fn lint_syntax_error() {
+        let diagnostics = lint("modLoader=\"javafml\"\nlicense=");
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].span.line, 2);
+    }
+}
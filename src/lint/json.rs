@@ -0,0 +1,214 @@
+use std::ops::Range;
+
+use super::{Key, Node, Value};
+
+/// Parse a JSON document keeping the byte range of every value and key,
+/// on failure returns the error message and where it occurred.
+pub(super) fn parse(source: &str) -> Result<Node> {
+    let mut parser = Parser { source, pos: 0 };
+    let node = parser.value()?;
+    parser.whitespace();
+    if parser.pos < source.len() {
+        return Err(parser.error("trailing characters after JSON value"));
+    }
+    Ok(node)
+}
+
+struct Parser<'a> {
+    source: &'a str,
+    pos: usize,
+}
+
+type Result<T> = std::result::Result<T, (String, Range<usize>)>;
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<char> {
+        self.source[self.pos..].chars().next()
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let ch = self.peek()?;
+        self.pos += ch.len_utf8();
+        Some(ch)
+    }
+
+    fn error(&self, message: &str) -> (String, Range<usize>) {
+        let end = self.peek().map_or(self.pos, |ch| self.pos + ch.len_utf8());
+        (message.to_string(), self.pos..end)
+    }
+
+    fn whitespace(&mut self) {
+        while self
+            .peek()
+            .is_some_and(|ch| matches!(ch, ' ' | '\t' | '\n' | '\r'))
+        {
+            self.pos += 1;
+        }
+    }
+
+    fn expect(&mut self, ch: char) -> Result<()> {
+        if self.peek() == Some(ch) {
+            self.pos += ch.len_utf8();
+            Ok(())
+        } else {
+            Err(self.error(&format!("expected '{ch}'")))
+        }
+    }
+
+    fn value(&mut self) -> Result<Node> {
+        self.whitespace();
+        let start = self.pos;
+
+        let value = match self.peek() {
+            Some('{') => self.object()?,
+            Some('[') => self.array()?,
+            Some('"') => Value::String(self.string()?),
+            Some('t') => self.keyword("true", Value::Bool(true))?,
+            Some('f') => self.keyword("false", Value::Bool(false))?,
+            Some('n') => self.keyword("null", Value::Null)?,
+            Some(ch) if ch == '-' || ch.is_ascii_digit() => self.number(),
+            Some(_) => return Err(self.error("expected a JSON value")),
+            None => return Err(self.error("unexpected end of input")),
+        };
+
+        Ok(Node {
+            span: start..self.pos,
+            value,
+        })
+    }
+
+    fn keyword(&mut self, keyword: &str, value: Value) -> Result<Value> {
+        if self.source[self.pos..].starts_with(keyword) {
+            self.pos += keyword.len();
+            Ok(value)
+        } else {
+            Err(self.error("expected a JSON value"))
+        }
+    }
+
+    fn number(&mut self) -> Value {
+        let start = self.pos;
+        while self
+            .peek()
+            .is_some_and(|ch| ch.is_ascii_digit() || matches!(ch, '-' | '+' | '.' | 'e' | 'E'))
+        {
+            self.pos += 1;
+        }
+        Value::Number(self.source[start..self.pos].to_string())
+    }
+
+    fn string(&mut self) -> Result<String> {
+        self.expect('"')?;
+        let mut s = String::new();
+
+        loop {
+            match self.bump() {
+                Some('"') => return Ok(s),
+                Some('\\') => match self.bump() {
+                    Some('"') => s.push('"'),
+                    Some('\\') => s.push('\\'),
+                    Some('/') => s.push('/'),
+                    Some('b') => s.push('\u{8}'),
+                    Some('f') => s.push('\u{c}'),
+                    Some('n') => s.push('\n'),
+                    Some('r') => s.push('\r'),
+                    Some('t') => s.push('\t'),
+                    Some('u') => {
+                        let hex = self.source.get(self.pos..self.pos + 4);
+                        let code = hex.and_then(|hex| u32::from_str_radix(hex, 16).ok());
+                        let Some(code) = code else {
+                            return Err(self.error("invalid unicode escape"));
+                        };
+                        self.pos += 4;
+                        s.push(char::from_u32(code).unwrap_or(char::REPLACEMENT_CHARACTER));
+                    }
+                    _ => return Err(self.error("invalid escape sequence")),
+                },
+                Some(ch) => s.push(ch),
+                None => return Err(self.error("unterminated string")),
+            }
+        }
+    }
+
+    fn array(&mut self) -> Result<Value> {
+        self.expect('[')?;
+        let mut items = vec![];
+
+        self.whitespace();
+        if self.peek() == Some(']') {
+            self.pos += 1;
+            return Ok(Value::Array(items));
+        }
+
+        loop {
+            items.push(self.value()?);
+            self.whitespace();
+            match self.peek() {
+                Some(',') => self.pos += 1,
+                Some(']') => {
+                    self.pos += 1;
+                    return Ok(Value::Array(items));
+                }
+                _ => return Err(self.error("expected ',' or ']'")),
+            }
+        }
+    }
+
+    fn object(&mut self) -> Result<Value> {
+        self.expect('{')?;
+        let mut entries = vec![];
+
+        self.whitespace();
+        if self.peek() == Some('}') {
+            self.pos += 1;
+            return Ok(Value::Object(entries));
+        }
+
+        loop {
+            self.whitespace();
+            let start = self.pos;
+            let name = self.string()?;
+            let key = Key {
+                span: start..self.pos,
+                name,
+            };
+
+            self.whitespace();
+            self.expect(':')?;
+            entries.push((key, self.value()?));
+
+            self.whitespace();
+            match self.peek() {
+                Some(',') => self.pos += 1,
+                Some('}') => {
+                    self.pos += 1;
+                    return Ok(Value::Object(entries));
+                }
+                _ => return Err(self.error("expected ',' or '}'")),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_spans() {
+        let source = r#"{ "id": "abc", "list": [1, true, null] }"#;
+        let node = parse(source).unwrap();
+
+        let (key, value) = &node.as_object().unwrap()[0];
+        assert_eq!(&source[key.span.clone()], r#""id""#);
+        assert_eq!(&source[value.span.clone()], r#""abc""#);
+
+        let list = node.get("list").unwrap().as_array().unwrap();
+        assert_eq!(list[1].value, Value::Bool(true));
+        assert_eq!(&source[list[2].span.clone()], "null");
+
+        let (message, span) = parse(r#"{ "id" "abc" }"#).unwrap_err();
+        assert_eq!(message, "expected ':'");
+        assert_eq!(span, 7..8);
+    }
+}
@@ -0,0 +1,56 @@
+use std::{error, fmt};
+
+/// A parse error or warning together with the input and where in it it was found.
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Located<E> {
+    pub error: E,
+    pub input: String,
+    /// Byte offset of `token` in `input`.
+    pub offset: usize,
+    /// The text at `offset` that could not be parsed, empty at the end of the input.
+    pub token: String,
+}
+
+impl<E> Located<E> {
+    pub fn new(error: E, input: &str, offset: usize, token: &str) -> Self {
+        Self {
+            error,
+            input: input.to_string(),
+            offset,
+            token: token.to_string(),
+        }
+    }
+
+    /// The input with the token underlined on the line below it, e.g.
+    ///
+    /// ```text
+    /// [1.0,2.0]]
+    ///          ^
+    /// ```
+    pub fn underline(&self) -> String {
+        let indent = self.input[..self.offset].chars().count();
+        let width = self.token.chars().count().max(1);
+        format!(
+            "{}\n{}{}",
+            self.input,
+            " ".repeat(indent),
+            "^".repeat(width)
+        )
+    }
+}
+
+impl<E: fmt::Display> fmt::Display for Located<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.token.is_empty() {
+            write!(f, "{} at end of input", self.error)
+        } else {
+            write!(
+                f,
+                "{} at `{}` (offset {})",
+                self.error, self.token, self.offset
+            )
+        }
+    }
+}
+
+impl<E: fmt::Debug + fmt::Display> error::Error for Located<E> {}
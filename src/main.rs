@@ -0,0 +1,713 @@
+use std::{
+    cmp,
+    error::Error,
+    fmt, fs,
+    io::{self, Write},
+    path::Path,
+    process::ExitCode,
+};
+
+use mod_version::{
+    curseforge::CfManifest,
+    fabric::{
+        self, Environment, FabricManifest,
+        version::{FabricVersion, FabricVersionRange, SemVer},
+    },
+    forge::{
+        self, UnsubstitutedForgeManifest, UnsubstitutedForgeVersion,
+        mcmod_info::McModInfo,
+        version::{ForgeVersion, ForgeVersionRange},
+    },
+    jar::{FORGE_MANIFEST, Jar, LoaderManifest, MCMOD_INFO, NEOFORGE_MANIFEST, QUILT_MANIFEST},
+    lint,
+    located::Located,
+    modrinth::MrManifest,
+    pack::{
+        check::{self, InstalledMod, Severity},
+        diff::PackDiff,
+    },
+    quilt::QuiltManifest,
+};
+use serde_json::json;
+
+const USAGE: &str = "\
+Usage: mod_version [--json] <command>
+
+Commands:
+    inspect <jar>                               Print the mod metadata in a jar
+    check <mods-dir>                            Check the dependencies of every mod in a directory
+    lint <file>                                 Lint a fabric.mod.json or mods.toml file
+    range [--fabric|--forge] <range> <version>  Check if a version is in a version range
+    compare [--fabric|--forge] <a> <b>          Compare two versions
+    pack diff <a> <b>                           Compare two modpack manifests
+
+Options:
+    --json      Print the output as JSON
+    --fabric    Use Fabric version semantics
+    --forge     Use Forge (Maven) version semantics";
+
+type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+fn main() -> ExitCode {
+    let args = std::env::args().skip(1).collect();
+
+    match run(args, &mut io::stdout().lock()) {
+        Ok(code) => code,
+        Err(e) => {
+            eprintln!("error: {e}");
+            ExitCode::from(2)
+        }
+    }
+}
+
+fn run(mut args: Vec<String>, out: &mut dyn Write) -> Result<ExitCode> {
+    let json = take_flag(&mut args, "--json");
+    let loader = Loader::take(&mut args)?;
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+
+    match args.as_slice() {
+        ["inspect", jar] => inspect(jar, json, out),
+        ["check", dir] => check(dir, json, out),
+        ["lint", path] => lint(path, json, out),
+        ["range", range, version] => range_matches(loader, range, version, json, out),
+        ["compare", a, b] => compare(loader, a, b, json, out),
+        ["pack", "diff", a, b] => pack_diff(a, b, json, out),
+        [] | ["help" | "--help" | "-h"] => {
+            writeln!(out, "{USAGE}")?;
+            Ok(ExitCode::SUCCESS)
+        }
+        _ => Err(format!("invalid arguments\n\n{USAGE}").into()),
+    }
+}
+
+fn take_flag(args: &mut Vec<String>, flag: &str) -> bool {
+    let len = args.len();
+    args.retain(|arg| arg != flag);
+    args.len() != len
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Loader {
+    Fabric,
+    Forge,
+}
+
+impl Loader {
+    fn take(args: &mut Vec<String>) -> Result<Option<Self>> {
+        match (take_flag(args, "--fabric"), take_flag(args, "--forge")) {
+            (true, true) => Err("--fabric and --forge cannot be used together".into()),
+            (true, false) => Ok(Some(Loader::Fabric)),
+            (false, true) => Ok(Some(Loader::Forge)),
+            (false, false) => Ok(None),
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Loader::Fabric => "fabric",
+            Loader::Forge => "forge",
+        }
+    }
+}
+
+fn inspect(path: &str, json: bool, out: &mut dyn Write) -> Result<ExitCode> {
+    let mut jar = Jar::open(path)?;
+    let detected = jar.detect();
+    // A malformed `MANIFEST.MF` is already in `detected.errors`.
+    let implementation_version = jar.implementation_version().ok().flatten();
+    let errors: Vec<String> = detected.errors.iter().map(ToString::to_string).collect();
+
+    if json {
+        let value = json!({
+            "loaders": detected.loaders,
+            "library": detected.library,
+            "implementation_version": implementation_version,
+            "errors": errors,
+        });
+        writeln!(out, "{}", serde_json::to_string_pretty(&value)?)?;
+    } else {
+        if detected.loaders.is_empty() && errors.is_empty() {
+            writeln!(out, "{path}: no mod metadata")?;
+        }
+        for manifest in &detected.loaders {
+            match manifest {
+                LoaderManifest::Fabric(manifest) => print_fabric(manifest, out)?,
+                LoaderManifest::Quilt(manifest) => print_quilt(manifest, out)?,
+                LoaderManifest::Forge(manifest) => print_forge(
+                    FORGE_MANIFEST,
+                    manifest,
+                    implementation_version.as_ref(),
+                    out,
+                )?,
+                LoaderManifest::NeoForge(manifest) => print_forge(
+                    NEOFORGE_MANIFEST,
+                    manifest,
+                    implementation_version.as_ref(),
+                    out,
+                )?,
+                LoaderManifest::LegacyForge(mods) => print_mcmod_info(mods, out)?,
+            }
+        }
+        for error in &errors {
+            writeln!(out, "error: {error}")?;
+        }
+    }
+
+    Ok(if errors.is_empty() {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    })
+}
+
+fn print_fabric(manifest: &FabricManifest, out: &mut dyn Write) -> io::Result<()> {
+    writeln!(out, "fabric.mod.json")?;
+    writeln!(out, "  id: {}", manifest.id)?;
+    if let Some(name) = &manifest.name {
+        writeln!(out, "  name: {name}")?;
+    }
+    writeln!(out, "  version: {}", manifest.version)?;
+    let environment = match manifest.environment {
+        Environment::Client => "client",
+        Environment::Server => "server",
+        Environment::Any => "*",
+    };
+    writeln!(out, "  environment: {environment}")?;
+    if !manifest.provides.is_empty() {
+        let provides: Vec<String> = manifest.provides.iter().map(ToString::to_string).collect();
+        writeln!(out, "  provides: {}", provides.join(", "))?;
+    }
+
+    for (name, deps) in [
+        ("depends", &manifest.depends),
+        ("recommends", &manifest.recommends),
+        ("suggests", &manifest.suggests),
+        ("breaks", &manifest.breaks),
+        ("conflicts", &manifest.conflicts),
+    ] {
+        let mut deps: Vec<_> = deps.iter().collect();
+        deps.sort_by(|a, b| a.0.cmp(b.0));
+        for (id, range) in deps {
+            writeln!(out, "  {name}: {id} {range}")?;
+        }
+    }
+    Ok(())
+}
+
+fn print_quilt(manifest: &QuiltManifest, out: &mut dyn Write) -> io::Result<()> {
+    let loader = &manifest.quilt_loader;
+    writeln!(out, "{QUILT_MANIFEST}")?;
+    writeln!(out, "  id: {}:{}", loader.group, loader.id)?;
+    if let Some(name) = &loader.metadata.name {
+        writeln!(out, "  name: {name}")?;
+    }
+    writeln!(out, "  version: {}", loader.version)
+}
+
+fn print_mcmod_info(mods: &[McModInfo], out: &mut dyn Write) -> io::Result<()> {
+    writeln!(out, "{MCMOD_INFO}")?;
+    for m in mods {
+        write!(out, "  mod: {}", m.mod_id)?;
+        if let Some(version) = &m.version {
+            write!(out, " {version}")?;
+        }
+        if let Some(name) = &m.name {
+            write!(out, " ({name})")?;
+        }
+        writeln!(out)?;
+    }
+    Ok(())
+}
+
+fn print_forge(
+    file: &str,
+    manifest: &UnsubstitutedForgeManifest,
+    implementation_version: Option<&ForgeVersion>,
+    out: &mut dyn Write,
+) -> io::Result<()> {
+    writeln!(out, "{file}")?;
+    writeln!(
+        out,
+        "  loader: {} {}",
+        manifest.mod_loader, manifest.loader_version
+    )?;
+    writeln!(out, "  license: {}", manifest.license)?;
+
+    for m in &manifest.mods {
+        let version = match (&m.version, implementation_version) {
+            (UnsubstitutedForgeVersion::ForgeVersion(version), _) => version.to_string(),
+            (UnsubstitutedForgeVersion::ImplementationVersion, Some(version)) => {
+                version.to_string()
+            }
+            (UnsubstitutedForgeVersion::ImplementationVersion, None) => {
+                "${file.jarVersion}".to_string()
+            }
+        };
+        write!(out, "  mod: {} {version}", m.mod_id)?;
+        if let Some(name) = &m.display_name {
+            write!(out, " ({name})")?;
+        }
+        writeln!(out)?;
+
+        for dep in manifest.dependencies.get(&m.mod_id.0).into_iter().flatten() {
+            let kind = if dep.is_mandatory() {
+                "requires"
+            } else {
+                "optional"
+            };
+            writeln!(out, "    {kind}: {} {}", dep.mod_id, dep.version_range)?;
+        }
+    }
+    Ok(())
+}
+
+fn check(dir: &str, json: bool, out: &mut dyn Write) -> Result<ExitCode> {
+    let (mods, mut problems) = InstalledMod::read_dir(dir)?;
+    problems.extend(check::check(&mods));
+    let failed = problems.iter().any(|p| p.severity == Severity::Error);
+
+    if json {
+        let value = json!({ "mods": mods, "problems": problems });
+        writeln!(out, "{}", serde_json::to_string_pretty(&value)?)?;
+    } else {
+        for problem in &problems {
+            writeln!(out, "{problem}")?;
+        }
+        writeln!(
+            out,
+            "checked {} mods, found {} problems",
+            mods.len(),
+            problems.len()
+        )?;
+    }
+
+    Ok(if failed {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    })
+}
+
+fn lint(path: &str, json: bool, out: &mut dyn Write) -> Result<ExitCode> {
+    let source = fs::read_to_string(path)?;
+    let diagnostics = if path.ends_with(".toml") {
+        lint::forge::lint(&source)
+    } else {
+        lint::fabric::lint(&source)
+    };
+    let failed = diagnostics
+        .iter()
+        .any(|d| d.severity == lint::Severity::Error);
+
+    if json {
+        writeln!(out, "{}", serde_json::to_string_pretty(&diagnostics)?)?;
+    } else {
+        for diagnostic in &diagnostics {
+            writeln!(out, "{path}:{diagnostic}")?;
+        }
+    }
+
+    Ok(if failed {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    })
+}
+
+fn range_matches(
+    loader: Option<Loader>,
+    range: &str,
+    version: &str,
+    json: bool,
+    out: &mut dyn Write,
+) -> Result<ExitCode> {
+    // Maven ranges are the only ones that use brackets.
+    let loader = loader.unwrap_or(if range.trim_start().starts_with(['[', '(']) {
+        Loader::Forge
+    } else {
+        Loader::Fabric
+    });
+
+    let matches = match loader {
+        Loader::Fabric => {
+            let range = FabricVersionRange::parse_single(range).map_err(|e| match e {
+                fabric::version::Error::Located(e) => underlined(&e),
+                e => e.into(),
+            })?;
+            range.matches(&FabricVersion::parse(version, false)?)
+        }
+        Loader::Forge => {
+            let range = ForgeVersionRange::parse(range).map_err(|e| match e {
+                forge::version::Error::Located(e) => underlined(&e),
+                e => e.into(),
+            })?;
+            range.matches(&ForgeVersion::parse(version)?)
+        }
+    };
+
+    if json {
+        let value = json!({
+            "loader": loader.name(),
+            "range": range,
+            "version": version,
+            "matches": matches,
+        });
+        writeln!(out, "{}", serde_json::to_string_pretty(&value)?)?;
+    } else if matches {
+        writeln!(out, "{version} matches {range}")?;
+    } else {
+        writeln!(out, "{version} does not match {range}")?;
+    }
+
+    Ok(if matches {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    })
+}
+
+/// The error followed by the input with the problem underlined.
+fn underlined<E: fmt::Display>(e: &Located<E>) -> Box<dyn Error> {
+    format!("{e}\n\n    {}", e.underline().replace('\n', "\n    ")).into()
+}
+
+fn compare(
+    loader: Option<Loader>,
+    a: &str,
+    b: &str,
+    json: bool,
+    out: &mut dyn Write,
+) -> Result<ExitCode> {
+    // Fabric falls back to comparing plain strings, so only use it when both are semantic versions.
+    let loader = loader.unwrap_or(
+        if SemVer::parse(a, false).is_ok() && SemVer::parse(b, false).is_ok() {
+            Loader::Fabric
+        } else {
+            Loader::Forge
+        },
+    );
+
+    let ord = match loader {
+        Loader::Fabric => FabricVersion::parse(a, false)?.cmp(&FabricVersion::parse(b, false)?),
+        Loader::Forge => ForgeVersion::parse(a)?.cmp(&ForgeVersion::parse(b)?),
+    };
+
+    if json {
+        let ordering = match ord {
+            cmp::Ordering::Less => "less",
+            cmp::Ordering::Equal => "equal",
+            cmp::Ordering::Greater => "greater",
+        };
+        let value = json!({ "loader": loader.name(), "a": a, "b": b, "ordering": ordering });
+        writeln!(out, "{}", serde_json::to_string_pretty(&value)?)?;
+    } else {
+        let symbol = match ord {
+            cmp::Ordering::Less => "<",
+            cmp::Ordering::Equal => "==",
+            cmp::Ordering::Greater => ">",
+        };
+        writeln!(out, "{a} {symbol} {b}")?;
+    }
+
+    Ok(ExitCode::SUCCESS)
+}
+
+enum Pack {
+    Modrinth(MrManifest),
+    CurseForge(CfManifest),
+}
+
+impl Pack {
+    /// Read a `modrinth.index.json` or CurseForge `manifest.json`,
+    /// either directly or from inside a `.mrpack` or `.zip` modpack.
+    fn read(path: &str) -> Result<Self> {
+        let path = Path::new(path);
+
+        let buf = if path.extension().is_some_and(|ext| ext == "json") {
+            fs::read(path)?
+        } else {
+            let mut zip = Jar::open(path)?;
+            match zip.read("modrinth.index.json")? {
+                Some(buf) => buf,
+                None => zip.read("manifest.json")?.ok_or_else(|| {
+                    format!(
+                        "{}: no modrinth.index.json or manifest.json",
+                        path.display()
+                    )
+                })?,
+            }
+        };
+
+        match serde_json::from_slice(&buf) {
+            Ok(manifest) => Ok(Pack::Modrinth(manifest)),
+            Err(_) => Ok(Pack::CurseForge(serde_json::from_slice(&buf).map_err(
+                |e| {
+                    format!(
+                        "{}: not a Modrinth or CurseForge manifest: {e}",
+                        path.display()
+                    )
+                },
+            )?)),
+        }
+    }
+}
+
+fn pack_diff(a: &str, b: &str, json: bool, out: &mut dyn Write) -> Result<ExitCode> {
+    let diff = match (Pack::read(a)?, Pack::read(b)?) {
+        (Pack::Modrinth(a), Pack::Modrinth(b)) => PackDiff::modrinth(&a, &b),
+        (Pack::CurseForge(a), Pack::CurseForge(b)) => PackDiff::curseforge(&a, &b),
+        _ => return Err("cannot compare a Modrinth modpack with a CurseForge modpack".into()),
+    };
+
+    if json {
+        writeln!(out, "{}", diff.to_json()?)?;
+    } else if diff.is_empty() {
+        writeln!(out, "no changes")?;
+    } else {
+        write!(out, "{}", diff.to_markdown())?;
+    }
+
+    Ok(ExitCode::SUCCESS)
+}
+
+#[cfg(test)]
+mod test {
+    use zip::{ZipWriter, write::SimpleFileOptions};
+
+    use super::*;
+
+    fn run_args(args: &[&str]) -> (ExitCode, String) {
+        let mut out = vec![];
+        let code = run(args.iter().map(ToString::to_string).collect(), &mut out).unwrap();
+        (code, String::from_utf8(out).unwrap())
+    }
+
+    fn run_err(args: &[&str]) -> String {
+        let args = args.iter().map(ToString::to_string).collect();
+        run(args, &mut vec![]).unwrap_err().to_string()
+    }
+
+    fn write_jar(path: &Path, files: &[(&str, &str)]) {
+        let mut writer = ZipWriter::new(fs::File::create(path).unwrap());
+        for (name, contents) in files {
+            writer
+                .start_file(*name, SimpleFileOptions::default())
+                .unwrap();
+            writer.write_all(contents.as_bytes()).unwrap();
+        }
+        writer.finish().unwrap();
+    }
+
+    #[test]
+    fn parse_arguments() {
+        let (code, out) = run_args(&["--help"]);
+        assert_eq!(code, ExitCode::SUCCESS);
+        assert_eq!(out, format!("{USAGE}\n"));
+
+        assert!(run_err(&["inspect"]).starts_with("invalid arguments"));
+        assert!(run_err(&["pack", "merge", "a", "b"]).starts_with("invalid arguments"));
+        assert_eq!(
+            run_err(&["compare", "--fabric", "--forge", "1", "2"]),
+            "--fabric and --forge cannot be used together"
+        );
+    }
+
+    #[test]
+    fn compare_command() {
+        assert_eq!(run_args(&["compare", "1.0", "1.0.1"]).1, "1.0 < 1.0.1\n");
+        assert_eq!(
+            run_args(&["compare", "--forge", "1.0", "1.0.0"]).1,
+            "1.0 == 1.0.0\n"
+        );
+        assert_eq!(
+            run_args(&["compare", "1.0-SNAPSHOT", "1.0"]).1,
+            "1.0-SNAPSHOT < 1.0\n"
+        );
+
+        let (_, out) = run_args(&["--json", "compare", "2", "1"]);
+        let value: serde_json::Value = serde_json::from_str(&out).unwrap();
+        assert_eq!(value["loader"], "fabric");
+        assert_eq!(value["ordering"], "greater");
+    }
+
+    #[test]
+    fn range_command() {
+        let (code, out) = run_args(&["range", "[1.0,2.0)", "1.5"]);
+        assert_eq!(code, ExitCode::SUCCESS);
+        assert_eq!(out, "1.5 matches [1.0,2.0)\n");
+
+        let (code, out) = run_args(&["range", "--fabric", ">=2", "1.0"]);
+        assert_eq!(code, ExitCode::FAILURE);
+        assert_eq!(out, "1.0 does not match >=2\n");
+
+        let (_, out) = run_args(&["--json", "range", "^1.2", "1.9"]);
+        let value: serde_json::Value = serde_json::from_str(&out).unwrap();
+        assert_eq!(value["loader"], "fabric");
+        assert_eq!(value["matches"], true);
+
+        assert!(run_err(&["range", "[1.0,2.0]]", "1.5"]).ends_with("\n             ^"));
+    }
+
+    #[test]
+    fn lint_command() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("fabric.mod.json");
+        fs::write(
+            &path,
+            r#"{ "schemaVersion": 1, "id": "examplemod", "version": "1.0.0", "depends": { "minecraft": ">=2 <1" } }"#,
+        )
+        .unwrap();
+        let path = path.to_str().unwrap();
+
+        let (code, out) = run_args(&["lint", path]);
+        assert_eq!(code, ExitCode::FAILURE);
+        assert!(out.starts_with(&format!("{path}:1:")), "{out}");
+        assert!(out.contains("can never match"), "{out}");
+
+        let (_, out) = run_args(&["--json", "lint", path]);
+        let value: serde_json::Value = serde_json::from_str(&out).unwrap();
+        assert_eq!(value.as_array().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn inspect_command() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("examplemod.jar");
+        write_jar(
+            &path,
+            &[
+                (
+                    "fabric.mod.json",
+                    r#"{ "schemaVersion": 1, "id": "examplemod", "version": "1.0.0" }"#,
+                ),
+                (
+                    "quilt.mod.json",
+                    r#"{ "schema_version": 1, "quilt_loader": { "group": "com.example", "id": "examplemod", "version": "1.0.0" } }"#,
+                ),
+                (
+                    "mcmod.info",
+                    r#"[{ "modid": "examplemod", "version": "1.0.0" }]"#,
+                ),
+                ("META-INF/neoforge.mods.toml", "modLoader="),
+            ],
+        );
+        let path = path.to_str().unwrap();
+
+        let (code, out) = run_args(&["inspect", path]);
+        assert_eq!(code, ExitCode::FAILURE);
+        assert!(
+            out.starts_with("fabric.mod.json\n  id: examplemod\n"),
+            "{out}"
+        );
+        assert!(
+            out.contains("quilt.mod.json\n  id: com.example:examplemod\n"),
+            "{out}"
+        );
+        assert!(
+            out.contains("mcmod.info\n  mod: examplemod 1.0.0\n"),
+            "{out}"
+        );
+        assert!(
+            out.contains("error: META-INF/neoforge.mods.toml: "),
+            "{out}"
+        );
+
+        let (_, out) = run_args(&["--json", "inspect", path]);
+        let value: serde_json::Value = serde_json::from_str(&out).unwrap();
+        assert_eq!(value["loaders"][0]["fabric"]["id"], "examplemod");
+        assert_eq!(value["loaders"][2]["legacyforge"][0]["modid"], "examplemod");
+        assert_eq!(value["errors"].as_array().unwrap().len(), 1);
+        assert_eq!(value["library"], false);
+    }
+
+    #[test]
+    fn check_command() {
+        let dir = tempfile::tempdir().unwrap();
+        write_jar(
+            &dir.path().join("examplemod.jar"),
+            &[(
+                "fabric.mod.json",
+                r#"{ "schemaVersion": 1, "id": "examplemod", "version": "1.0.0", "depends": { "othermod": ">=2" } }"#,
+            )],
+        );
+        write_jar(
+            &dir.path().join("othermod.jar"),
+            &[(
+                "META-INF/neoforge.mods.toml",
+                r#"
+                modLoader="javafml"
+                loaderVersion="[1,)"
+                license="MIT"
+
+                [[mods]]
+                modId="othermod"
+                version="1.5"
+                "#,
+            )],
+        );
+        fs::write(dir.path().join("broken.jar"), "not a zip").unwrap();
+        let dir = dir.path().to_str().unwrap();
+
+        let (code, out) = run_args(&["check", dir]);
+        assert_eq!(code, ExitCode::FAILURE);
+        let lines: Vec<&str> = out.lines().collect();
+        assert!(
+            lines[0].starts_with("error: broken.jar: could not be read: "),
+            "{out}"
+        );
+        assert_eq!(
+            lines[1..],
+            [
+                "error: examplemod: requires othermod >=2 but 1.5 is installed",
+                "checked 2 mods, found 2 problems",
+            ]
+        );
+
+        let (_, out) = run_args(&["--json", "check", dir]);
+        let value: serde_json::Value = serde_json::from_str(&out).unwrap();
+        assert_eq!(value["mods"][0]["id"], "examplemod");
+        assert_eq!(value["mods"][1]["id"], "othermod");
+        assert_eq!(value["problems"][0]["kind"], "unreadable");
+        assert_eq!(value["problems"][1]["kind"], "mismatch");
+    }
+
+    #[test]
+    fn pack_diff_command() {
+        let dir = tempfile::tempdir().unwrap();
+        let (a, b) = (dir.path().join("a.json"), dir.path().join("b.json"));
+        fs::write(
+            &a,
+            r#"{
+                "formatVersion": 1,
+                "game": "minecraft",
+                "versionId": "1",
+                "name": "Pack",
+                "files": [],
+                "dependencies": { "minecraft": "1.20.1", "fabric-loader": "0.14.23" }
+            }"#,
+        )
+        .unwrap();
+        fs::write(
+            &b,
+            r#"{
+                "formatVersion": 1,
+                "game": "minecraft",
+                "versionId": "2",
+                "name": "Pack",
+                "files": [],
+                "dependencies": { "minecraft": "1.20.4", "fabric-loader": "0.14.23" }
+            }"#,
+        )
+        .unwrap();
+        let (a, b) = (a.to_str().unwrap(), b.to_str().unwrap());
+
+        let (code, out) = run_args(&["pack", "diff", a, b]);
+        assert_eq!(code, ExitCode::SUCCESS);
+        assert_eq!(out, "## Dependencies\n\n- minecraft: 1.20.1 → 1.20.4\n\n");
+        assert_eq!(run_args(&["pack", "diff", a, a]).1, "no changes\n");
+
+        let (_, out) = run_args(&["--json", "pack", "diff", a, b]);
+        let value: serde_json::Value = serde_json::from_str(&out).unwrap();
+        assert_eq!(value["dependencies"][0]["new"], "1.20.4");
+    }
+}
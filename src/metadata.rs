@@ -0,0 +1,422 @@
+use std::fmt;
+
+use crate::{
+    fabric::{
+        FabricManifest,
+        version::{FabricVersion, FabricVersionRange},
+    },
+    forge::{
+        DependencyType, ForgeManifest, Mod, Ordering,
+        version::{ForgeVersion, ForgeVersionRange},
+    },
+};
+
+/// Metadata of a single mod, independent of the loader it was declared for.
+pub trait ModMetadata {
+    fn id(&self) -> &str;
+    fn name(&self) -> Option<&str>;
+    fn version(&self) -> Version;
+    fn description(&self) -> Option<&str>;
+    fn authors(&self) -> Vec<&str>;
+    /// Where the mod needs to be installed.
+    fn side(&self) -> Side;
+    /// Whether the mod is loaded on `side`, e.g. `loads_on(Side::Server)` for a dedicated server.
+    fn loads_on(&self, side: Side) -> bool {
+        self.side().applies_to(side)
+    }
+    /// Other ids this mod can satisfy dependencies for.
+    fn provides(&self) -> Vec<&str> {
+        vec![]
+    }
+    fn dependencies(&self) -> Vec<Dependency>;
+    /// The dependencies that apply on `side`.
+    fn dependencies_on(&self, side: Side) -> Vec<Dependency> {
+        self.dependencies()
+            .into_iter()
+            .filter(|dep| dep.side.applies_to(side))
+            .collect()
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub enum Version {
+    Fabric(FabricVersion),
+    Forge(ForgeVersion),
+}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Version::Fabric(version) => write!(f, "{version}"),
+            Version::Forge(version) => write!(f, "{version}"),
+        }
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub enum VersionRange {
+    Fabric(FabricVersionRange),
+    Forge(ForgeVersionRange),
+}
+
+impl VersionRange {
+    /// Check if a version string is in the range,
+    /// the version is parsed using the rules of the loader the range came from.
+    pub fn matches(&self, version: &str) -> bool {
+        match self {
+            VersionRange::Fabric(range) => {
+                FabricVersion::parse(version, false).is_ok_and(|version| range.matches(&version))
+            }
+            VersionRange::Forge(range) => {
+                ForgeVersion::parse(version).is_ok_and(|version| range.matches(&version))
+            }
+        }
+    }
+}
+
+impl fmt::Display for VersionRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VersionRange::Fabric(range) => write!(f, "{range}"),
+            VersionRange::Forge(range) => write!(f, "{range}"),
+        }
+    }
+}
+
+/// A side of the game, shared by Fabric's `environment` and Forge's `side`.
+#[derive(PartialEq, Eq, Debug, Clone, Copy, Default)]
+pub enum Side {
+    #[default]
+    Both,
+    Client,
+    Server,
+}
+
+impl Side {
+    /// Whether something declared for this side is present on `side`,
+    /// `Both` overlaps every side, e.g. `Side::Both.applies_to(Side::Server)` is `true`.
+    pub fn applies_to(self, side: Side) -> bool {
+        self == Side::Both || side == Side::Both || self == side
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Dependency {
+    pub id: String,
+    pub kind: DependencyKind,
+    /// The range as declared in the loader's manifest.
+    pub range: VersionRange,
+    pub side: Side,
+    /// Where the mod is loaded relative to the dependency, only Forge declares it.
+    pub ordering: Option<LoadOrder>,
+}
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum DependencyKind {
+    /// Must be installed with a version in the range.
+    Required,
+    /// Does not need to be installed, Fabric's `recommends` and `suggests`.
+    Optional,
+    /// Must not be installed with a version in the range, Fabric's `breaks`.
+    Incompatible,
+    /// Should not be installed with a version in the range, Fabric's `conflicts`
+    /// and NeoForge's `discouraged`.
+    Discouraged,
+}
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum LoadOrder {
+    Before,
+    After,
+}
+
+/// Check that a mod id is 2 to 64 characters, starts with a lowercase letter
+/// and otherwise only contains `a-z`, `0-9` and the characters in `allowed`.
+pub fn validate_mod_id(id: &str, allowed: &'static [char]) -> Result<(), ModIdError> {
+    if id.len() < 2 {
+        return Err(ModIdError::TooShort);
+    }
+    if id.len() > 64 {
+        return Err(ModIdError::TooLong);
+    }
+
+    let first = id.chars().next().unwrap_or_default();
+    if !first.is_ascii_lowercase() {
+        return Err(ModIdError::InvalidStart(first));
+    }
+    match id
+        .chars()
+        .find(|ch| !matches!(ch, 'a'..='z' | '0'..='9') && !allowed.contains(ch))
+    {
+        Some(found) => Err(ModIdError::InvalidChar { found, allowed }),
+        None => Ok(()),
+    }
+}
+
+#[derive(thiserror::Error, PartialEq, Eq, Debug)]
+pub enum ModIdError {
+    #[error("mod id must be at least 2 characters")]
+    TooShort,
+    #[error("mod id must be at most 64 characters")]
+    TooLong,
+    #[error("mod id must start with a lowercase letter, found `{0}`")]
+    InvalidStart(char),
+    #[error(
+        "mod id contains `{found}`, only `a-z`, `0-9`{} are allowed",
+        .allowed.iter().map(|ch| format!(", `{ch}`")).collect::<String>()
+    )]
+    InvalidChar {
+        found: char,
+        allowed: &'static [char],
+    },
+}
+
+impl ModMetadata for FabricManifest {
+    fn id(&self) -> &str {
+        &self.id.0
+    }
+
+    fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    fn version(&self) -> Version {
+        Version::Fabric(self.version.clone())
+    }
+
+    fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    fn authors(&self) -> Vec<&str> {
+        self.authors.iter().map(|person| person.name()).collect()
+    }
+
+    fn side(&self) -> Side {
+        self.environment.into()
+    }
+
+    fn provides(&self) -> Vec<&str> {
+        self.provides.iter().map(|id| id.0.as_str()).collect()
+    }
+
+    fn dependencies(&self) -> Vec<Dependency> {
+        let mut dependencies = vec![];
+        for (kind, deps) in [
+            (DependencyKind::Required, &self.depends),
+            (DependencyKind::Optional, &self.recommends),
+            (DependencyKind::Optional, &self.suggests),
+            (DependencyKind::Incompatible, &self.breaks),
+            (DependencyKind::Discouraged, &self.conflicts),
+        ] {
+            for (id, range) in deps {
+                dependencies.push(Dependency {
+                    id: id.clone(),
+                    kind,
+                    range: VersionRange::Fabric(range.clone()),
+                    side: Side::Both,
+                    ordering: None,
+                });
+            }
+        }
+        dependencies.sort_by(|a, b| a.id.cmp(&b.id));
+        dependencies
+    }
+}
+
+/// A `[[mods]]` entry together with the manifest it was declared in,
+/// which holds its dependencies.
+#[derive(Debug, Clone, Copy)]
+pub struct ForgeMod<'a> {
+    pub manifest: &'a ForgeManifest,
+    pub mod_info: &'a Mod,
+}
+
+impl ForgeManifest {
+    /// The metadata of every mod in the manifest.
+    pub fn metadata(&self) -> impl Iterator<Item = ForgeMod<'_>> {
+        self.mods.iter().map(|mod_info| ForgeMod {
+            manifest: self,
+            mod_info,
+        })
+    }
+}
+
+impl ModMetadata for ForgeMod<'_> {
+    fn id(&self) -> &str {
+        &self.mod_info.mod_id.0
+    }
+
+    fn name(&self) -> Option<&str> {
+        self.mod_info.display_name.as_deref()
+    }
+
+    fn version(&self) -> Version {
+        Version::Forge(self.mod_info.version.clone())
+    }
+
+    fn description(&self) -> Option<&str> {
+        self.mod_info.description.as_deref()
+    }
+
+    /// Forge only has a single free-form string, by convention authors are separated by commas.
+    fn authors(&self) -> Vec<&str> {
+        self.mod_info
+            .authors
+            .iter()
+            .flat_map(|authors| authors.split(','))
+            .map(str::trim)
+            .filter(|author| !author.is_empty())
+            .collect()
+    }
+
+    /// Taken from `displayTest`, which is the closest Forge has to a declared side.
+    fn side(&self) -> Side {
+        self.mod_info.display_test.unwrap_or_default().side()
+    }
+
+    fn dependencies(&self) -> Vec<Dependency> {
+        self.manifest
+            .dependencies
+            .get(&self.mod_info.mod_id.0)
+            .into_iter()
+            .flatten()
+            .map(|dep| Dependency {
+                id: dep.mod_id.clone(),
+                kind: match dep.dependency_type {
+                    Some(DependencyType::Incompatible) => DependencyKind::Incompatible,
+                    Some(DependencyType::Discouraged) => DependencyKind::Discouraged,
+                    _ if dep.is_mandatory() => DependencyKind::Required,
+                    _ => DependencyKind::Optional,
+                },
+                range: VersionRange::Forge(dep.version_range.clone()),
+                side: dep.side.into(),
+                ordering: match dep.ordering {
+                    Ordering::None => None,
+                    Ordering::Before => Some(LoadOrder::Before),
+                    Ordering::After => Some(LoadOrder::After),
+                },
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::forge::UnsubstitutedForgeManifest;
+
+    #[test]
+    fn validate_mod_id_rules() {
+        assert_eq!(validate_mod_id("a", &[]), Err(ModIdError::TooShort));
+        assert_eq!(
+            validate_mod_id(&"a".repeat(65), &[]),
+            Err(ModIdError::TooLong)
+        );
+        assert_eq!(
+            validate_mod_id("Example", &[]),
+            Err(ModIdError::InvalidStart('E'))
+        );
+        assert_eq!(
+            validate_mod_id("2example", &[]),
+            Err(ModIdError::InvalidStart('2'))
+        );
+        assert_eq!(validate_mod_id("example.mod", &['.']), Ok(()));
+    }
+
+    #[test]
+    fn fabric_metadata() {
+        let manifest: FabricManifest = serde_json::from_str(
+            r#"{
+                "schemaVersion": 1,
+                "id": "examplemod",
+                "version": "1.0.0",
+                "environment": "client",
+                "authors": ["Me", { "name": "You" }],
+                "depends": { "minecraft": "1.20.1" },
+                "suggests": { "modmenu": "*" },
+                "breaks": { "optifabric": "*" }
+            }"#,
+        )
+        .unwrap();
+
+        let metadata: &dyn ModMetadata = &manifest;
+        assert_eq!(metadata.id(), "examplemod");
+        assert_eq!(metadata.version().to_string(), "1.0.0");
+        assert_eq!(metadata.authors(), vec!["Me", "You"]);
+        assert_eq!(metadata.side(), Side::Client);
+        assert!(!metadata.loads_on(Side::Server));
+
+        let kinds: Vec<_> = metadata
+            .dependencies()
+            .into_iter()
+            .map(|dep| (dep.id, dep.kind))
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                ("minecraft".into(), DependencyKind::Required),
+                ("modmenu".into(), DependencyKind::Optional),
+                ("optifabric".into(), DependencyKind::Incompatible),
+            ]
+        );
+    }
+
+    #[test]
+    fn forge_metadata() {
+        let manifest: UnsubstitutedForgeManifest = toml::from_str(
+            r#"
+            modLoader="javafml"
+            loaderVersion="[41,)"
+            license="MIT"
+
+            [[mods]]
+            modId="examplemod"
+            version="1.0"
+            displayName="Example Mod"
+            authors="Me, You"
+
+            [[dependencies.examplemod]]
+            modId="minecraft"
+            mandatory=true
+            versionRange="[1.19,1.20)"
+            side="CLIENT"
+            ordering="BEFORE"
+
+            [[dependencies.examplemod]]
+            modId="jei"
+            mandatory=false
+            versionRange="[1,)"
+            ordering="AFTER"
+
+            [[dependencies.examplemod]]
+            modId="optifine"
+            type="discouraged"
+            versionRange="[1,)"
+            "#,
+        )
+        .unwrap();
+        let manifest = manifest.substitute(None).unwrap();
+
+        let metadata: Vec<ForgeMod> = manifest.metadata().collect();
+        assert_eq!(metadata.len(), 1);
+        assert_eq!(metadata[0].name(), Some("Example Mod"));
+        assert_eq!(metadata[0].authors(), vec!["Me", "You"]);
+
+        let dependencies = metadata[0].dependencies();
+        assert_eq!(dependencies[0].kind, DependencyKind::Required);
+        assert!(metadata[0].loads_on(Side::Server));
+        assert_eq!(metadata[0].dependencies_on(Side::Server).len(), 2);
+        assert_eq!(metadata[0].dependencies_on(Side::Client).len(), 3);
+        assert_eq!(dependencies[0].side, Side::Client);
+        assert_eq!(
+            dependencies[0].range,
+            VersionRange::Forge(ForgeVersionRange::parse("[1.19,1.20)").unwrap())
+        );
+        assert_eq!(dependencies[0].ordering, Some(LoadOrder::Before));
+        assert_eq!(dependencies[1].kind, DependencyKind::Optional);
+        assert_eq!(dependencies[1].ordering, Some(LoadOrder::After));
+        assert_eq!(dependencies[2].kind, DependencyKind::Discouraged);
+        assert_eq!(dependencies[2].ordering, None);
+    }
+}
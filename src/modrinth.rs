@@ -2,8 +2,10 @@ use std::{collections::HashMap, path::PathBuf};
 
 use serde::{Deserialize, Serialize};
 
+pub mod api;
+
 /// Modrinth modpack manifest file: `modrinth.index.json`
-#[derive(Serialize, Deserialize, PartialEq, Eq, Debug)]
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct MrManifest {
     pub format_version: u64,
@@ -15,7 +17,7 @@ pub struct MrManifest {
     pub dependencies: HashMap<String, String>,
 }
 
-#[derive(Serialize, Deserialize, PartialEq, Eq, Debug)]
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct File {
     pub path: PathBuf,
@@ -25,13 +27,26 @@ pub struct File {
     pub file_size: u64,
 }
 
-#[derive(Serialize, Deserialize, PartialEq, Eq, Debug)]
+impl File {
+    /// The Modrinth project id, taken from a `cdn.modrinth.com` download url.
+    pub fn project_id(&self) -> Option<&str> {
+        self.downloads.iter().find_map(|url| {
+            let rest = url
+                .strip_prefix("https://cdn.modrinth.com/data/")
+                .or_else(|| url.strip_prefix("http://cdn.modrinth.com/data/"))?;
+            let (id, rest) = rest.split_once('/')?;
+            rest.starts_with("versions/").then_some(id)
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone)]
 pub struct Env {
     pub client: ProjectSupportRange,
     pub server: ProjectSupportRange,
 }
 
-#[derive(Deserialize, Serialize, PartialEq, Eq, Debug)]
+#[derive(Deserialize, Serialize, PartialEq, Eq, Debug, Clone, Copy)]
 #[serde(rename_all = "lowercase")]
 pub enum ProjectSupportRange {
     Required,
@@ -0,0 +1,313 @@
+use std::{cmp, collections::HashMap};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    fabric::version::FabricVersion, forge::version::ForgeVersion, modrinth::ProjectSupportRange,
+};
+
+/// A version from `GET /v2/version/{id}`, `GET /v2/project/{id}/version`
+/// or `GET /v2/version_file/{hash}`.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone)]
+pub struct Version {
+    pub id: String,
+    pub project_id: String,
+    pub author_id: Option<String>,
+    pub name: String,
+    pub version_number: String,
+    pub changelog: Option<String>,
+    #[serde(default)]
+    pub featured: bool,
+    pub version_type: VersionType,
+    pub status: Option<String>,
+    pub date_published: String,
+    #[serde(default)]
+    pub downloads: u64,
+    pub game_versions: Vec<String>,
+    pub loaders: Vec<String>,
+    pub files: Vec<VersionFile>,
+    #[serde(default)]
+    pub dependencies: Vec<Dependency>,
+}
+
+/// The response of `POST /v2/version_files`, file hashes mapped to the version they belong to.
+pub type VersionFiles = HashMap<String, Version>;
+
+/// Ordered from least to most stable.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone, Copy, PartialOrd, Ord)]
+#[serde(rename_all = "lowercase")]
+pub enum VersionType {
+    Alpha,
+    Beta,
+    Release,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone)]
+pub struct VersionFile {
+    /// Hash algorithm (`sha1`, `sha512`) mapped to the hex digest.
+    pub hashes: HashMap<String, String>,
+    pub url: String,
+    pub filename: String,
+    pub primary: bool,
+    pub size: u64,
+    pub file_type: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone)]
+pub struct Dependency {
+    pub version_id: Option<String>,
+    pub project_id: Option<String>,
+    pub file_name: Option<String>,
+    pub dependency_type: DependencyType,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum DependencyType {
+    Required,
+    Optional,
+    Incompatible,
+    /// The dependency is bundled inside the file.
+    Embedded,
+}
+
+/// A project from `GET /v2/project/{id}`.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone)]
+pub struct Project {
+    pub id: String,
+    pub slug: String,
+    pub project_type: String,
+    pub title: String,
+    pub description: String,
+    pub client_side: ProjectSupportRange,
+    pub server_side: ProjectSupportRange,
+    #[serde(default)]
+    pub categories: Vec<String>,
+    #[serde(default)]
+    pub game_versions: Vec<String>,
+    #[serde(default)]
+    pub loaders: Vec<String>,
+    /// Ids of every version of the project.
+    #[serde(default)]
+    pub versions: Vec<String>,
+    #[serde(default)]
+    pub downloads: u64,
+}
+
+impl Version {
+    /// The primary file, or the first one if none is marked as primary.
+    pub fn primary_file(&self) -> Option<&VersionFile> {
+        self.files
+            .iter()
+            .find(|file| file.primary)
+            .or_else(|| self.files.first())
+    }
+
+    pub fn supports(&self, loader: &str, game_version: &str) -> bool {
+        self.loaders.iter().any(|l| l == loader)
+            && self.game_versions.iter().any(|v| v == game_version)
+    }
+
+    /// Compare version numbers with the rules of the loader,
+    /// falling back to the publish date when they are equal.
+    pub fn cmp_for_loader(&self, other: &Self, loader: &str) -> cmp::Ordering {
+        let (a, b) = (&self.version_number, &other.version_number);
+        let ord = match loader {
+            "fabric" | "quilt" => cmp_parsed(a, b, |s| FabricVersion::parse(s, false).ok()),
+            _ => cmp_parsed(a, b, |s| ForgeVersion::parse(s).ok()),
+        };
+        ord.then_with(|| self.date_published.cmp(&other.date_published))
+    }
+}
+
+/// Compare two versions with `parse`, versions that do not parse are older than all
+/// parsed versions and ordered by their string.
+fn cmp_parsed<T: Ord>(a: &str, b: &str, parse: impl Fn(&str) -> Option<T>) -> cmp::Ordering {
+    match (parse(a), parse(b)) {
+        (Some(a), Some(b)) => a.cmp(&b),
+        (Some(_), None) => cmp::Ordering::Greater,
+        (None, Some(_)) => cmp::Ordering::Less,
+        (None, None) => a.cmp(b),
+    }
+}
+
+/// Pick the newest version that supports the loader and Minecraft version,
+/// ignoring versions less stable than `least_stable`.
+pub fn select_update<'a>(
+    candidates: &'a [Version],
+    loader: &str,
+    game_version: &str,
+    least_stable: VersionType,
+) -> Option<&'a Version> {
+    candidates
+        .iter()
+        .filter(|version| version.version_type >= least_stable)
+        .filter(|version| version.supports(loader, game_version))
+        .max_by(|a, b| a.cmp_for_loader(b, loader))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_project() {
+        let project: Project = serde_json::from_str(
+            r#"{
+                "id": "AANobbMI",
+                "slug": "sodium",
+                "project_type": "mod",
+                "title": "Sodium",
+                "description": "The fastest rendering engine",
+                "client_side": "required",
+                "server_side": "unsupported",
+                "categories": ["optimization"],
+                "game_versions": ["1.20.1"],
+                "loaders": ["fabric", "quilt"],
+                "versions": ["a", "b"],
+                "downloads": 1000
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(project.server_side, ProjectSupportRange::Unsupported);
+        assert_eq!(project.versions.len(), 2);
+    }
+
+    #[test]
+    fn select_newest_compatible() {
+        let versions: Vec<Version> = serde_json::from_str(
+            r#"[
+                {
+                    "id": "a",
+                    "project_id": "AANobbMI",
+                    "name": "Sodium 0.5.3",
+                    "version_number": "0.5.3",
+                    "version_type": "release",
+                    "date_published": "2024-01-01T00:00:00Z",
+                    "game_versions": ["1.20.1"],
+                    "loaders": ["fabric"],
+                    "files": []
+                },
+                {
+                    "id": "b",
+                    "project_id": "AANobbMI",
+                    "author_id": "uhPSqlnd",
+                    "name": "Sodium 0.5.10",
+                    "version_number": "0.5.10",
+                    "changelog": null,
+                    "featured": false,
+                    "version_type": "release",
+                    "status": "listed",
+                    "date_published": "2024-01-01T00:00:00Z",
+                    "downloads": 100,
+                    "game_versions": ["1.20.1"],
+                    "loaders": ["fabric", "quilt"],
+                    "files": [{
+                        "hashes": { "sha1": "abc", "sha512": "def" },
+                        "url": "https://cdn.modrinth.com/data/AANobbMI/versions/b/sodium.jar",
+                        "filename": "sodium.jar",
+                        "primary": true,
+                        "size": 1000,
+                        "file_type": null
+                    }],
+                    "dependencies": [{
+                        "version_id": null,
+                        "project_id": "P7dR8mSH",
+                        "file_name": null,
+                        "dependency_type": "required"
+                    }]
+                },
+                {
+                    "id": "c",
+                    "project_id": "AANobbMI",
+                    "name": "Sodium 0.6.0-beta.1",
+                    "version_number": "0.6.0-beta.1",
+                    "version_type": "beta",
+                    "date_published": "2024-01-01T00:00:00Z",
+                    "game_versions": ["1.20.1"],
+                    "loaders": ["fabric"],
+                    "files": []
+                },
+                {
+                    "id": "d",
+                    "project_id": "AANobbMI",
+                    "name": "Sodium 0.7.0",
+                    "version_number": "0.7.0",
+                    "version_type": "release",
+                    "date_published": "2024-01-01T00:00:00Z",
+                    "game_versions": ["1.20.1"],
+                    "loaders": ["neoforge"],
+                    "files": []
+                }
+            ]"#,
+        )
+        .unwrap();
+        assert_eq!(
+            versions[1].dependencies[0].dependency_type,
+            DependencyType::Required
+        );
+        assert_eq!(versions[1].primary_file().unwrap().hashes["sha1"], "abc");
+
+        let select = |loader, game_version, least_stable| {
+            select_update(&versions, loader, game_version, least_stable).map(|v| v.id.as_str())
+        };
+        // 0.5.10 is newer than 0.5.3 even though it sorts before it as a string.
+        assert_eq!(select("fabric", "1.20.1", VersionType::Release), Some("b"));
+        assert_eq!(select("fabric", "1.20.1", VersionType::Beta), Some("c"));
+        assert_eq!(select("quilt", "1.20.1", VersionType::Alpha), Some("b"));
+        assert_eq!(
+            select("neoforge", "1.20.1", VersionType::Release),
+            Some("d")
+        );
+        assert_eq!(select("fabric", "1.19.2", VersionType::Alpha), None);
+    }
+
+    #[test]
+    fn unparsable_versions_are_oldest() {
+        let versions: Vec<Version> = serde_json::from_str(
+            r#"[
+                {
+                    "id": "a",
+                    "project_id": "AANobbMI",
+                    "name": "Sodium",
+                    "version_number": "1.0",
+                    "version_type": "release",
+                    "date_published": "2024-01-01T00:00:00Z",
+                    "game_versions": ["1.20.1"],
+                    "loaders": ["fabric", "neoforge"],
+                    "files": []
+                },
+                {
+                    "id": "b",
+                    "project_id": "AANobbMI",
+                    "name": "Sodium",
+                    "version_number": " ",
+                    "version_type": "release",
+                    "date_published": "2024-01-01T00:00:00Z",
+                    "game_versions": ["1.20.1"],
+                    "loaders": ["fabric", "neoforge"],
+                    "files": []
+                },
+                {
+                    "id": "c",
+                    "project_id": "AANobbMI",
+                    "name": "Sodium",
+                    "version_number": "",
+                    "version_type": "release",
+                    "date_published": "2024-01-01T00:00:00Z",
+                    "game_versions": ["1.20.1"],
+                    "loaders": ["fabric", "neoforge"],
+                    "files": []
+                }
+            ]"#,
+        )
+        .unwrap();
+        let (parsed, blank, empty) = (&versions[0], &versions[1], &versions[2]);
+        for loader in ["fabric", "neoforge"] {
+            assert_eq!(blank.cmp_for_loader(parsed, loader), cmp::Ordering::Less);
+            assert_eq!(parsed.cmp_for_loader(blank, loader), cmp::Ordering::Greater);
+            assert_eq!(empty.cmp_for_loader(blank, loader), cmp::Ordering::Less);
+            assert_eq!(blank.cmp_for_loader(blank, loader), cmp::Ordering::Equal);
+        }
+    }
+}
@@ -0,0 +1,3 @@
+pub mod check;
+pub mod diff;
+pub mod server;
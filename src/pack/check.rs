@@ -0,0 +1,472 @@
+use std::{
+    collections::HashMap,
+    fmt,
+    path::{Path, PathBuf},
+};
+
+use serde::Serialize;
+
+use crate::{
+    fabric::FabricManifest,
+    forge::ForgeManifest,
+    jar::{self, Jar, LoaderManifest},
+    metadata::{DependencyKind, ModMetadata, VersionRange},
+};
+
+/// Mod ids that are provided by the game or the mod loader instead of a jar.
+const PLATFORM_IDS: &[&str] = &[
+    "minecraft",
+    "java",
+    "fabricloader",
+    "fabric-loader",
+    "forge",
+    "neoforge",
+];
+
+/// A mod read from a jar, used to check the dependencies between mods.
+#[derive(Serialize, PartialEq, Eq, Debug, Clone)]
+pub struct InstalledMod {
+    pub path: PathBuf,
+    pub id: String,
+    /// Other ids this mod can satisfy dependencies for.
+    pub provides: Vec<String>,
+    pub version: String,
+    pub dependencies: Vec<Requirement>,
+}
+
+/// A dependency of an [`InstalledMod`] on another mod.
+#[derive(Serialize, PartialEq, Eq, Debug, Clone)]
+pub struct Requirement {
+    pub id: String,
+    pub kind: RequirementKind,
+    #[serde(serialize_with = "serialize_display")]
+    pub range: VersionRange,
+}
+
+#[derive(Serialize, PartialEq, Eq, Debug, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum RequirementKind {
+    /// Must be installed and match the range.
+    Required,
+    /// Should be installed and match the range.
+    Recommended,
+    /// Does not need to be installed but must match the range when it is.
+    Optional,
+    /// Must not be installed with a version in the range.
+    Breaks,
+    /// Should not be installed with a version in the range.
+    Conflicts,
+}
+
+fn serialize_display<T: fmt::Display, S: serde::Serializer>(
+    value: &T,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.collect_str(value)
+}
+
+impl InstalledMod {
+    pub fn from_fabric(path: PathBuf, manifest: &FabricManifest) -> Self {
+        let mut dependencies = vec![];
+        for (kind, deps) in [
+            (RequirementKind::Required, &manifest.depends),
+            (RequirementKind::Recommended, &manifest.recommends),
+            (RequirementKind::Breaks, &manifest.breaks),
+            (RequirementKind::Conflicts, &manifest.conflicts),
+        ] {
+            for (id, range) in deps {
+                dependencies.push(Requirement {
+                    id: id.to_string(),
+                    kind,
+                    range: VersionRange::Fabric(range.clone()),
+                });
+            }
+        }
+        dependencies.sort_by(|a, b| a.id.cmp(&b.id));
+
+        Self {
+            path,
+            id: manifest.id.to_string(),
+            provides: manifest.provides.iter().map(ToString::to_string).collect(),
+            version: manifest.version.to_string(),
+            dependencies,
+        }
+    }
+
+    pub fn from_forge(path: &Path, manifest: &ForgeManifest) -> Vec<Self> {
+        manifest
+            .metadata()
+            .map(|m| {
+                let dependencies = m
+                    .dependencies()
+                    .into_iter()
+                    .map(|dep| Requirement {
+                        id: dep.id,
+                        kind: match dep.kind {
+                            DependencyKind::Required => RequirementKind::Required,
+                            DependencyKind::Incompatible => RequirementKind::Breaks,
+                            DependencyKind::Discouraged => RequirementKind::Conflicts,
+                            DependencyKind::Optional => RequirementKind::Optional,
+                        },
+                        range: dep.range,
+                    })
+                    .collect();
+
+                Self {
+                    path: path.to_path_buf(),
+                    id: m.id().to_string(),
+                    provides: vec![],
+                    version: m.version().to_string(),
+                    dependencies,
+                }
+            })
+            .collect()
+    }
+
+    /// Read every mod in a jar, the fabric metadata is preferred over Forge and NeoForge
+    /// metadata when a jar has several.
+    pub fn from_jar(path: impl AsRef<Path>) -> jar::Result<Vec<Self>> {
+        let path = path.as_ref();
+        let mut jar = Jar::open(path)?;
+        let mut detected = jar.detect();
+
+        for manifest in detected.loaders {
+            match manifest {
+                LoaderManifest::Fabric(manifest) => {
+                    return Ok(vec![Self::from_fabric(path.to_path_buf(), &manifest)]);
+                }
+                LoaderManifest::Forge(manifest) | LoaderManifest::NeoForge(manifest) => {
+                    let version = jar.implementation_version()?;
+                    let manifest = manifest.substitute(version.as_ref())?;
+                    return Ok(Self::from_forge(path, &manifest));
+                }
+                _ => {}
+            }
+        }
+
+        if detected.errors.is_empty() {
+            Ok(vec![])
+        } else {
+            Err(detected.errors.remove(0))
+        }
+    }
+
+    /// Read every mod from the `.jar` files in a directory,
+    /// jars that cannot be read are returned as problems instead of stopping the scan.
+    pub fn read_dir(dir: impl AsRef<Path>) -> jar::Result<(Vec<Self>, Vec<Problem>)> {
+        let mut paths = vec![];
+        for entry in std::fs::read_dir(dir)? {
+            let path = entry?.path();
+            if path.extension().is_some_and(|ext| ext == "jar") {
+                paths.push(path);
+            }
+        }
+        paths.sort();
+
+        let mut mods = vec![];
+        let mut problems = vec![];
+        for path in paths {
+            match Self::from_jar(&path) {
+                Ok(jar_mods) => mods.extend(jar_mods),
+                Err(e) => problems.push(Problem {
+                    severity: Severity::Error,
+                    mod_id: path
+                        .file_name()
+                        .unwrap_or_default()
+                        .to_string_lossy()
+                        .into_owned(),
+                    kind: ProblemKind::Unreadable {
+                        error: e.to_string(),
+                    },
+                    path,
+                }),
+            }
+        }
+        Ok((mods, problems))
+    }
+}
+
+/// A problem found by [`check`].
+#[derive(Serialize, PartialEq, Eq, Debug, Clone)]
+pub struct Problem {
+    pub severity: Severity,
+    /// The mod that declared the dependency.
+    pub mod_id: String,
+    pub path: PathBuf,
+    #[serde(flatten)]
+    pub kind: ProblemKind,
+}
+
+#[derive(Serialize, PartialEq, Eq, Debug, Clone, Copy, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum Severity {
+    Warning,
+    Error,
+}
+
+#[derive(Serialize, PartialEq, Eq, Debug, Clone)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ProblemKind {
+    /// A dependency is not installed.
+    Missing { dependency: String, range: String },
+    /// A dependency is installed but not with a version in the range.
+    Mismatch {
+        dependency: String,
+        range: String,
+        found: String,
+    },
+    /// An incompatible mod is installed.
+    Incompatible {
+        dependency: String,
+        range: String,
+        found: String,
+    },
+    /// Another jar provides the same mod id.
+    Duplicate { other: PathBuf },
+    /// The jar or its metadata could not be read, `mod_id` is the file name of the jar.
+    Unreadable { error: String },
+}
+
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let severity = match self.severity {
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        };
+        write!(f, "{severity}: {}: ", self.mod_id)?;
+
+        match &self.kind {
+            ProblemKind::Missing { dependency, range } => {
+                write!(f, "requires {dependency} {range} but it is not installed")
+            }
+            ProblemKind::Mismatch {
+                dependency,
+                range,
+                found,
+            } => write!(f, "requires {dependency} {range} but {found} is installed"),
+            ProblemKind::Incompatible {
+                dependency,
+                range,
+                found,
+            } => write!(
+                f,
+                "is incompatible with {dependency} {range} but {found} is installed"
+            ),
+            ProblemKind::Duplicate { other } => write!(
+                f,
+                "is provided by both {} and {}",
+                self.path.display(),
+                other.display()
+            ),
+            ProblemKind::Unreadable { error } => write!(f, "could not be read: {error}"),
+        }
+    }
+}
+
+/// Check that the dependencies of every mod are satisfied by the other mods.
+pub fn check(mods: &[InstalledMod]) -> Vec<Problem> {
+    let mut problems = vec![];
+    let mut installed: HashMap<&str, &InstalledMod> = HashMap::new();
+
+    for m in mods {
+        for id in std::iter::once(&m.id).chain(&m.provides) {
+            match installed.get(id.as_str()) {
+                Some(other) if other.path != m.path => problems.push(Problem {
+                    severity: Severity::Error,
+                    mod_id: id.clone(),
+                    path: m.path.clone(),
+                    kind: ProblemKind::Duplicate {
+                        other: other.path.clone(),
+                    },
+                }),
+                Some(_) => {}
+                None => {
+                    installed.insert(id, m);
+                }
+            }
+        }
+    }
+
+    for m in mods {
+        for dep in &m.dependencies {
+            let found = installed.get(dep.id.as_str());
+            let range = dep.range.to_string();
+
+            let (severity, kind) = match (dep.kind, found) {
+                (RequirementKind::Required | RequirementKind::Recommended, None) => {
+                    if PLATFORM_IDS.contains(&dep.id.as_str()) {
+                        continue;
+                    }
+                    (
+                        severity(dep.kind),
+                        ProblemKind::Missing {
+                            dependency: dep.id.clone(),
+                            range,
+                        },
+                    )
+                }
+                (
+                    RequirementKind::Required
+                    | RequirementKind::Recommended
+                    | RequirementKind::Optional,
+                    Some(found),
+                ) if !dep.range.matches(&found.version) => (
+                    severity(dep.kind),
+                    ProblemKind::Mismatch {
+                        dependency: dep.id.clone(),
+                        range,
+                        found: found.version.clone(),
+                    },
+                ),
+                (RequirementKind::Breaks | RequirementKind::Conflicts, Some(found))
+                    if dep.range.matches(&found.version) =>
+                {
+                    (
+                        severity(dep.kind),
+                        ProblemKind::Incompatible {
+                            dependency: dep.id.clone(),
+                            range,
+                            found: found.version.clone(),
+                        },
+                    )
+                }
+                _ => continue,
+            };
+
+            problems.push(Problem {
+                severity,
+                mod_id: m.id.clone(),
+                path: m.path.clone(),
+                kind,
+            });
+        }
+    }
+
+    problems
+}
+
+fn severity(kind: RequirementKind) -> Severity {
+    match kind {
+        RequirementKind::Required | RequirementKind::Optional | RequirementKind::Breaks => {
+            Severity::Error
+        }
+        RequirementKind::Recommended | RequirementKind::Conflicts => Severity::Warning,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::forge::{UnsubstitutedForgeManifest, version::ForgeVersion};
+
+    fn fabric(s: &str) -> InstalledMod {
+        let manifest: FabricManifest = serde_json::from_str(s).unwrap();
+        InstalledMod::from_fabric(format!("{}.jar", manifest.id).into(), &manifest)
+    }
+
+    #[test]
+    fn check_fabric() {
+        let mods = [
+            fabric(
+                r#"{
+                    "schemaVersion": 1,
+                    "id": "iris",
+                    "version": "1.6.11",
+                    "depends": { "sodium": ">=0.5.8", "minecraft": "1.20.1" },
+                    "recommends": { "modmenu": "*" },
+                    "breaks": { "optifabric": "*" }
+                }"#,
+            ),
+            fabric(r#"{ "schemaVersion": 1, "id": "sodium", "version": "0.5.3" }"#),
+            fabric(r#"{ "schemaVersion": 1, "id": "optifabric", "version": "1.0.0" }"#),
+        ];
+
+        let problems = check(&mods);
+        assert_eq!(
+            problems.iter().map(ToString::to_string).collect::<Vec<_>>(),
+            vec![
+                "warning: iris: requires modmenu * but it is not installed",
+                "error: iris: is incompatible with optifabric * but 1.0.0 is installed",
+                "error: iris: requires sodium >=0.5.8 but 0.5.3 is installed",
+            ]
+        );
+    }
+
+    #[test]
+    fn check_forge() {
+        let manifest: UnsubstitutedForgeManifest = toml::from_str(
+            r#"
+            modLoader="javafml"
+            loaderVersion="[41,)"
+            license="MIT"
+
+            [[mods]]
+            modId="examplemod"
+            version="${file.jarVersion}"
+
+            [[dependencies.examplemod]]
+                modId="forge"
+                mandatory=true
+                versionRange="[41,)"
+
+            [[dependencies.examplemod]]
+                modId="jei"
+                mandatory=false
+                versionRange="[11,)"
+
+            [[dependencies.examplemod]]
+                modId="examplemod"
+                mandatory=true
+                versionRange="[2,)"
+
+            [[dependencies.examplemod]]
+                modId="optifine"
+                type="discouraged"
+                versionRange="*"
+            "#,
+        )
+        .unwrap();
+        let manifest = manifest
+            .substitute(Some(&ForgeVersion::parse("1.0").unwrap()))
+            .unwrap();
+        let mut mods = InstalledMod::from_forge(Path::new("examplemod.jar"), &manifest);
+        mods.extend(InstalledMod::from_forge(Path::new("dupe.jar"), &manifest));
+        assert_eq!(
+            mods[0].dependencies[3].kind,
+            RequirementKind::Conflicts,
+            "discouraged is a warning, not an error"
+        );
+
+        let problems = check(&mods);
+        assert_eq!(
+            problems
+                .iter()
+                .map(|p| (p.path.to_str().unwrap(), &p.kind))
+                .collect::<Vec<_>>(),
+            vec![
+                (
+                    "dupe.jar",
+                    &ProblemKind::Duplicate {
+                        other: "examplemod.jar".into()
+                    }
+                ),
+                (
+                    "examplemod.jar",
+                    &ProblemKind::Mismatch {
+                        dependency: "examplemod".into(),
+                        range: "[2,)".into(),
+                        found: "1.0".into(),
+                    }
+                ),
+                (
+                    "dupe.jar",
+                    &ProblemKind::Mismatch {
+                        dependency: "examplemod".into(),
+                        range: "[2,)".into(),
+                        found: "1.0".into(),
+                    }
+                ),
+            ]
+        );
+    }
+}
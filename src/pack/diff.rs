@@ -0,0 +1,640 @@
+use std::{
+    collections::{BTreeSet, HashMap, HashSet},
+    fmt::{self, Write as _},
+    path::Path,
+};
+
+use serde::Serialize;
+
+use crate::{
+    compat,
+    curseforge::{self, CfManifest},
+    modrinth::{self, Env, MrManifest, ProjectSupportRange},
+};
+
+/// The changes between two versions of a modpack.
+#[derive(Serialize, PartialEq, Eq, Debug, Default)]
+pub struct PackDiff {
+    pub files: Vec<FileChange>,
+    pub dependencies: Vec<DependencyChange>,
+}
+
+/// A change to a single file in a modpack.
+/// The `minecraft` versions are guessed from the file names with [`compat::from_file_name`].
+#[derive(Serialize, PartialEq, Eq, Debug, Clone)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum FileChange {
+    Added {
+        name: String,
+        version: Option<String>,
+        minecraft: Option<String>,
+    },
+    Removed {
+        name: String,
+        version: Option<String>,
+        minecraft: Option<String>,
+    },
+    Updated {
+        name: String,
+        old_version: Option<String>,
+        new_version: Option<String>,
+        old_minecraft: Option<String>,
+        new_minecraft: Option<String>,
+    },
+    EnvChanged {
+        name: String,
+        old_env: Option<Env>,
+        new_env: Option<Env>,
+    },
+}
+
+/// A change to a loader or minecraft version the modpack depends on.
+/// `old` is `None` when the dependency was added and `new` is `None` when it was removed.
+#[derive(Serialize, PartialEq, Eq, Debug, Clone)]
+pub struct DependencyChange {
+    pub id: String,
+    pub old: Option<String>,
+    pub new: Option<String>,
+}
+
+impl FileChange {
+    pub fn name(&self) -> &str {
+        match self {
+            FileChange::Added { name, .. }
+            | FileChange::Removed { name, .. }
+            | FileChange::Updated { name, .. }
+            | FileChange::EnvChanged { name, .. } => name,
+        }
+    }
+}
+
+impl PackDiff {
+    /// Diff two Modrinth modpacks.
+    ///
+    /// Files are matched by their Modrinth project id when it can be read from the download url,
+    /// otherwise, or when a pack has several files of the same project, by their path.
+    pub fn modrinth(old: &MrManifest, new: &MrManifest) -> Self {
+        fn repeated_projects(files: &[modrinth::File]) -> HashSet<&str> {
+            let mut seen = HashSet::new();
+            files
+                .iter()
+                .filter_map(modrinth::File::project_id)
+                .filter(|id| !seen.insert(*id))
+                .collect()
+        }
+
+        let repeated = &repeated_projects(&old.files) | &repeated_projects(&new.files);
+        let key = |file: &modrinth::File| match file.project_id() {
+            Some(id) if !repeated.contains(id) => id.to_string(),
+            _ => file.path.to_string_lossy().into_owned(),
+        };
+
+        let new_files: HashMap<String, &modrinth::File> =
+            new.files.iter().map(|file| (key(file), file)).collect();
+        let old_keys: BTreeSet<String> = old.files.iter().map(&key).collect();
+
+        let mut files = vec![];
+
+        for old_file in &old.files {
+            let name = file_name(&old_file.path);
+
+            let Some(new_file) = new_files.get(&key(old_file)) else {
+                files.push(FileChange::Removed {
+                    version: version_from_file_name(&name),
+                    minecraft: minecraft_from_file_name(&name),
+                    name,
+                });
+                continue;
+            };
+
+            if old_file.path != new_file.path || old_file.hashes != new_file.hashes {
+                let new_name = file_name(&new_file.path);
+                files.push(FileChange::Updated {
+                    old_version: version_from_file_name(&name),
+                    new_version: version_from_file_name(&new_name),
+                    old_minecraft: minecraft_from_file_name(&name),
+                    new_minecraft: minecraft_from_file_name(&new_name),
+                    name: name.clone(),
+                });
+            }
+
+            if old_file.env != new_file.env {
+                files.push(FileChange::EnvChanged {
+                    name,
+                    old_env: old_file.env.clone(),
+                    new_env: new_file.env.clone(),
+                });
+            }
+        }
+
+        for new_file in &new.files {
+            if !old_keys.contains(&key(new_file)) {
+                let name = file_name(&new_file.path);
+                files.push(FileChange::Added {
+                    version: version_from_file_name(&name),
+                    minecraft: minecraft_from_file_name(&name),
+                    name,
+                });
+            }
+        }
+
+        Self {
+            files,
+            dependencies: diff_dependencies(&old.dependencies, &new.dependencies),
+        }
+    }
+
+    /// Diff two CurseForge modpacks.
+    ///
+    /// Files are matched by their project id, the manifest only contains file ids
+    /// so those are used as the version strings.
+    pub fn curseforge(old: &CfManifest, new: &CfManifest) -> Self {
+        let new_files: HashMap<u64, &curseforge::File> = new
+            .files
+            .iter()
+            .map(|file| (file.project_id, file))
+            .collect();
+        let old_ids: BTreeSet<u64> = old.files.iter().map(|file| file.project_id).collect();
+
+        let mut files = vec![];
+
+        for old_file in &old.files {
+            let name = format!("project {}", old_file.project_id);
+
+            match new_files.get(&old_file.project_id) {
+                Some(new_file) if new_file.file_id != old_file.file_id => {
+                    files.push(FileChange::Updated {
+                        name,
+                        old_version: Some(old_file.file_id.to_string()),
+                        new_version: Some(new_file.file_id.to_string()),
+                        old_minecraft: None,
+                        new_minecraft: None,
+                    })
+                }
+                Some(_) => {}
+                None => files.push(FileChange::Removed {
+                    name,
+                    version: Some(old_file.file_id.to_string()),
+                    minecraft: None,
+                }),
+            }
+        }
+
+        for new_file in &new.files {
+            if !old_ids.contains(&new_file.project_id) {
+                files.push(FileChange::Added {
+                    name: format!("project {}", new_file.project_id),
+                    version: Some(new_file.file_id.to_string()),
+                    minecraft: None,
+                });
+            }
+        }
+
+        Self {
+            files,
+            dependencies: diff_dependencies(&cf_dependencies(old), &cf_dependencies(new)),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty() && self.dependencies.is_empty()
+    }
+
+    /// Render the diff as a Markdown changelog.
+    pub fn to_markdown(&self) -> String {
+        let mut out = String::new();
+        // Writing to a `String` never fails.
+        let _ = self.write_markdown(&mut out);
+        out
+    }
+
+    /// Render the diff as a JSON document.
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+
+    fn write_markdown(&self, f: &mut String) -> fmt::Result {
+        if !self.dependencies.is_empty() {
+            writeln!(f, "## Dependencies\n")?;
+            for DependencyChange { id, old, new } in &self.dependencies {
+                match (old, new) {
+                    (Some(old), Some(new)) => writeln!(f, "- {id}: {old} → {new}")?,
+                    (None, Some(new)) => writeln!(f, "- {id}: added {new}")?,
+                    (Some(old), None) => writeln!(f, "- {id}: removed {old}")?,
+                    (None, None) => writeln!(f, "- {id}")?,
+                }
+            }
+            writeln!(f)?;
+        }
+
+        let section = |f: &mut String, title: &str, filter: fn(&FileChange) -> bool| {
+            let mut changes = self.files.iter().filter(|x| filter(x)).peekable();
+            if changes.peek().is_none() {
+                return Ok(());
+            }
+
+            writeln!(f, "## {title}\n")?;
+            for change in changes {
+                write!(f, "- {}", change.name())?;
+                match change {
+                    FileChange::Added { version, .. } | FileChange::Removed { version, .. } => {
+                        if let Some(version) = version {
+                            write!(f, " ({version})")?;
+                        }
+                    }
+                    FileChange::Updated {
+                        old_version,
+                        new_version,
+                        ..
+                    } => {
+                        if old_version.is_some() || new_version.is_some() {
+                            let old = old_version.as_deref().unwrap_or("?");
+                            let new = new_version.as_deref().unwrap_or("?");
+                            write!(f, ": {old} → {new}")?;
+                        }
+                    }
+                    FileChange::EnvChanged {
+                        old_env, new_env, ..
+                    } => write!(f, ": {} → {}", EnvDisplay(old_env), EnvDisplay(new_env))?,
+                }
+                writeln!(f)?;
+            }
+            writeln!(f)
+        };
+
+        section(f, "Added", |x| matches!(x, FileChange::Added { .. }))?;
+        section(f, "Removed", |x| matches!(x, FileChange::Removed { .. }))?;
+        section(f, "Updated", |x| matches!(x, FileChange::Updated { .. }))?;
+        section(f, "Environment changes", |x| {
+            matches!(x, FileChange::EnvChanged { .. })
+        })?;
+
+        Ok(())
+    }
+}
+
+struct EnvDisplay<'a>(&'a Option<Env>);
+
+impl fmt::Display for EnvDisplay<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fn support(range: ProjectSupportRange) -> &'static str {
+            match range {
+                ProjectSupportRange::Required => "required",
+                ProjectSupportRange::Optional => "optional",
+                ProjectSupportRange::Unsupported => "unsupported",
+                ProjectSupportRange::Unknown => "unknown",
+            }
+        }
+
+        match self.0 {
+            Some(Env { client, server }) => write!(
+                f,
+                "client {}, server {}",
+                support(*client),
+                support(*server)
+            ),
+            None => write!(f, "unspecified"),
+        }
+    }
+}
+
+fn diff_dependencies(
+    old: &HashMap<String, String>,
+    new: &HashMap<String, String>,
+) -> Vec<DependencyChange> {
+    let ids: BTreeSet<&String> = old.keys().chain(new.keys()).collect();
+
+    ids.into_iter()
+        .filter_map(|id| {
+            let (old, new) = (old.get(id), new.get(id));
+            (old != new).then(|| DependencyChange {
+                id: id.clone(),
+                old: old.cloned(),
+                new: new.cloned(),
+            })
+        })
+        .collect()
+}
+
+/// The minecraft and mod loader versions of a CurseForge modpack,
+/// keyed the same way as the `dependencies` of a Modrinth modpack where possible.
+fn cf_dependencies(manifest: &CfManifest) -> HashMap<String, String> {
+    let mut deps = HashMap::new();
+    deps.insert("minecraft".to_string(), manifest.minecraft.version.clone());
+
+    for loader in &manifest.minecraft.mod_loaders {
+        let (id, version) = loader.id.split_once('-').unwrap_or((&loader.id, ""));
+        let id = match id {
+            "fabric" => "fabric-loader",
+            "quilt" => "quilt-loader",
+            id => id,
+        };
+        deps.insert(id.to_string(), version.to_string());
+    }
+
+    deps
+}
+
+fn file_name(path: &Path) -> String {
+    match path.file_name() {
+        Some(name) => name.to_string_lossy().into_owned(),
+        None => path.to_string_lossy().into_owned(),
+    }
+}
+
+fn minecraft_from_file_name(name: &str) -> Option<String> {
+    compat::from_file_name(name).and_then(|guess| guess.minecraft)
+}
+
+/// Guess the version from a file name, keeping the build metadata so a change
+/// of only the Minecraft version still shows, e.g `sodium-fabric-0.5.3+mc1.20.1.jar` is `0.5.3+mc1.20.1`
+fn version_from_file_name(name: &str) -> Option<String> {
+    let stem = name.strip_suffix(".jar").unwrap_or(name);
+
+    stem.char_indices()
+        .find(|(i, ch)| {
+            matches!(ch, '-' | '_') && stem[i + 1..].starts_with(|ch: char| ch.is_ascii_digit())
+        })
+        .map(|(i, _)| stem[i + 1..].to_string())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn diff_modrinth() {
+        let client_only = Env {
+            client: ProjectSupportRange::Required,
+            server: ProjectSupportRange::Unsupported,
+        };
+
+        let old: MrManifest = serde_json::from_str(
+            r#"{
+                "formatVersion": 1,
+                "game": "minecraft",
+                "versionId": "1",
+                "name": "Pack",
+                "files": [
+                    {
+                        "path": "mods/sodium-fabric-0.5.3+mc1.20.1.jar",
+                        "hashes": { "sha1": "a" },
+                        "downloads": ["https://cdn.modrinth.com/data/AANobbMI/versions/abc/sodium-fabric-0.5.3+mc1.20.1.jar"],
+                        "fileSize": 0
+                    },
+                    {
+                        "path": "mods/lithium-fabric-mc1.20.1-0.11.2.jar",
+                        "hashes": { "sha1": "b" },
+                        "downloads": ["https://cdn.modrinth.com/data/gvQqBUqZ/versions/abc/lithium-fabric-mc1.20.1-0.11.2.jar"],
+                        "fileSize": 0
+                    },
+                    {
+                        "path": "mods/modmenu-7.2.2.jar",
+                        "hashes": { "sha1": "c" },
+                        "downloads": ["https://cdn.modrinth.com/data/mOgUt4GM/versions/abc/modmenu-7.2.2.jar"],
+                        "fileSize": 0
+                    }
+                ],
+                "dependencies": { "minecraft": "1.20.1", "fabric-loader": "0.14.23" }
+            }"#,
+        )
+        .unwrap();
+        let new: MrManifest = serde_json::from_str(
+            r#"{
+                "formatVersion": 1,
+                "game": "minecraft",
+                "versionId": "2",
+                "name": "Pack",
+                "files": [
+                    {
+                        "path": "mods/sodium-fabric-0.5.8+mc1.20.1.jar",
+                        "hashes": { "sha1": "d" },
+                        "downloads": ["https://cdn.modrinth.com/data/AANobbMI/versions/abc/sodium-fabric-0.5.8+mc1.20.1.jar"],
+                        "fileSize": 0
+                    },
+                    {
+                        "path": "mods/modmenu-7.2.2.jar",
+                        "hashes": { "sha1": "c" },
+                        "env": { "client": "required", "server": "unsupported" },
+                        "downloads": ["https://cdn.modrinth.com/data/mOgUt4GM/versions/abc/modmenu-7.2.2.jar"],
+                        "fileSize": 0
+                    },
+                    {
+                        "path": "mods/iris-1.6.11+mc1.20.1.jar",
+                        "hashes": { "sha1": "e" },
+                        "downloads": ["https://cdn.modrinth.com/data/YL57xq9U/versions/abc/iris-1.6.11+mc1.20.1.jar"],
+                        "fileSize": 0
+                    }
+                ],
+                "dependencies": { "minecraft": "1.20.4", "fabric-loader": "0.14.23" }
+            }"#,
+        )
+        .unwrap();
+
+        let diff = PackDiff::modrinth(&old, &new);
+        assert_eq!(
+            diff.files,
+            vec![
+                FileChange::Updated {
+                    name: "sodium-fabric-0.5.3+mc1.20.1.jar".into(),
+                    old_version: Some("0.5.3+mc1.20.1".into()),
+                    new_version: Some("0.5.8+mc1.20.1".into()),
+                    old_minecraft: Some("1.20.1".into()),
+                    new_minecraft: Some("1.20.1".into()),
+                },
+                FileChange::Removed {
+                    name: "lithium-fabric-mc1.20.1-0.11.2.jar".into(),
+                    version: Some("0.11.2".into()),
+                    minecraft: Some("1.20.1".into()),
+                },
+                FileChange::EnvChanged {
+                    name: "modmenu-7.2.2.jar".into(),
+                    old_env: None,
+                    new_env: Some(client_only),
+                },
+                FileChange::Added {
+                    name: "iris-1.6.11+mc1.20.1.jar".into(),
+                    version: Some("1.6.11+mc1.20.1".into()),
+                    minecraft: Some("1.20.1".into()),
+                },
+            ]
+        );
+        assert_eq!(
+            diff.dependencies,
+            vec![DependencyChange {
+                id: "minecraft".into(),
+                old: Some("1.20.1".into()),
+                new: Some("1.20.4".into()),
+            }]
+        );
+
+        assert_eq!(
+            diff.to_markdown(),
+            "## Dependencies\n\n\
+            - minecraft: 1.20.1 → 1.20.4\n\n\
+            ## Added\n\n\
+            - iris-1.6.11+mc1.20.1.jar (1.6.11+mc1.20.1)\n\n\
+            ## Removed\n\n\
+            - lithium-fabric-mc1.20.1-0.11.2.jar (0.11.2)\n\n\
+            ## Updated\n\n\
+            - sodium-fabric-0.5.3+mc1.20.1.jar: 0.5.3+mc1.20.1 → 0.5.8+mc1.20.1\n\n\
+            ## Environment changes\n\n\
+            - modmenu-7.2.2.jar: unspecified → client required, server unsupported\n\n"
+        );
+
+        let json: serde_json::Value = serde_json::from_str(&diff.to_json().unwrap()).unwrap();
+        assert_eq!(json["files"][0]["kind"], "updated");
+        assert_eq!(json["files"][1]["minecraft"], "1.20.1");
+        assert_eq!(json["files"][2]["new_env"]["server"], "unsupported");
+    }
+
+    #[test]
+    fn diff_modrinth_files_of_one_project() {
+        let old: MrManifest = serde_json::from_str(
+            r#"{
+                "formatVersion": 1,
+                "game": "minecraft",
+                "versionId": "1",
+                "name": "Pack",
+                "files": [
+                    {
+                        "path": "mods/create-0.5.1.jar",
+                        "hashes": { "sha1": "a" },
+                        "downloads": ["https://cdn.modrinth.com/data/LNytGWDc/versions/abc/create-0.5.1.jar"],
+                        "fileSize": 0
+                    },
+                    {
+                        "path": "mods/create-textures-1.0.jar",
+                        "hashes": { "sha1": "b" },
+                        "downloads": ["https://cdn.modrinth.com/data/LNytGWDc/versions/def/create-textures-1.0.jar"],
+                        "fileSize": 0
+                    }
+                ],
+                "dependencies": { "minecraft": "1.20.1" }
+            }"#,
+        )
+        .unwrap();
+        let new: MrManifest = serde_json::from_str(
+            r#"{
+                "formatVersion": 1,
+                "game": "minecraft",
+                "versionId": "2",
+                "name": "Pack",
+                "files": [
+                    {
+                        "path": "mods/create-0.5.1.jar",
+                        "hashes": { "sha1": "a" },
+                        "downloads": ["https://cdn.modrinth.com/data/LNytGWDc/versions/abc/create-0.5.1.jar"],
+                        "fileSize": 0
+                    },
+                    {
+                        "path": "mods/create-textures-1.0.jar",
+                        "hashes": { "sha1": "b" },
+                        "downloads": ["https://cdn.modrinth.com/data/LNytGWDc/versions/def/create-textures-1.0.jar"],
+                        "fileSize": 0
+                    },
+                    {
+                        "path": "mods/create-shaders-2.0.jar",
+                        "hashes": { "sha1": "c" },
+                        "downloads": ["https://cdn.modrinth.com/data/LNytGWDc/versions/ghi/create-shaders-2.0.jar"],
+                        "fileSize": 0
+                    }
+                ],
+                "dependencies": { "minecraft": "1.20.1" }
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            PackDiff::modrinth(&old, &new).files,
+            vec![FileChange::Added {
+                name: "create-shaders-2.0.jar".into(),
+                version: Some("2.0".into()),
+                minecraft: None,
+            }]
+        );
+    }
+
+    #[test]
+    fn version_keeps_minecraft_version() {
+        assert_eq!(
+            version_from_file_name("sodium-0.5.3+mc1.20.1.jar"),
+            Some("0.5.3+mc1.20.1".into())
+        );
+        assert_ne!(
+            version_from_file_name("sodium-0.5.3+mc1.20.1.jar"),
+            version_from_file_name("sodium-0.5.3+mc1.20.4.jar")
+        );
+    }
+
+    #[test]
+    fn diff_curseforge() {
+        let manifest = |minecraft: &str, loader: &str, files: Vec<(u64, u64)>| CfManifest {
+            minecraft: curseforge::Minecraft {
+                version: minecraft.into(),
+                mod_loaders: vec![curseforge::ModLoader {
+                    id: loader.into(),
+                    primary: true,
+                }],
+            },
+            manifest_type: "minecraftModpack".into(),
+            manifest_version: 1,
+            name: "Pack".into(),
+            version: "1".into(),
+            author: "author".into(),
+            files: files
+                .into_iter()
+                .map(|(project_id, file_id)| curseforge::File {
+                    project_id,
+                    file_id,
+                    required: true,
+                })
+                .collect(),
+            overrides: "overrides".into(),
+        };
+
+        let old = manifest("1.20.1", "fabric-0.14.23", vec![(1, 10), (2, 20)]);
+        let new = manifest("1.20.1", "fabric-0.15.0", vec![(1, 11), (3, 30)]);
+
+        let diff = PackDiff::curseforge(&old, &new);
+        assert_eq!(
+            diff.files,
+            vec![
+                FileChange::Updated {
+                    name: "project 1".into(),
+                    old_version: Some("10".into()),
+                    new_version: Some("11".into()),
+                    old_minecraft: None,
+                    new_minecraft: None,
+                },
+                FileChange::Removed {
+                    name: "project 2".into(),
+                    version: Some("20".into()),
+                    minecraft: None,
+                },
+                FileChange::Added {
+                    name: "project 3".into(),
+                    version: Some("30".into()),
+                    minecraft: None,
+                },
+            ]
+        );
+        assert_eq!(
+            diff.dependencies,
+            vec![DependencyChange {
+                id: "fabric-loader".into(),
+                old: Some("0.14.23".into()),
+                new: Some("0.15.0".into()),
+            }]
+        );
+
+        let neoforge = manifest("1.20.4", "neoforge-20.4.80", vec![]);
+        assert_eq!(
+            cf_dependencies(&neoforge),
+            HashMap::from([
+                ("minecraft".into(), "1.20.4".into()),
+                ("neoforge".into(), "20.4.80".into())
+            ])
+        );
+    }
+}
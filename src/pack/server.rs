@@ -0,0 +1,397 @@
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use serde::Serialize;
+
+use crate::{
+    fabric::FabricManifest,
+    forge::{DisplayTest, UnsubstitutedForgeManifest},
+    jar::{self, Jar, LoaderManifest},
+    metadata::Side,
+    modrinth::{self, Env, MrManifest, ProjectSupportRange},
+};
+
+/// Which sides of the game a mod should be installed on.
+#[derive(Serialize, PartialEq, Eq, Debug, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum Placement {
+    Both,
+    ClientOnly,
+    ServerOnly,
+}
+
+impl Placement {
+    pub fn on_server(self) -> bool {
+        self != Placement::ClientOnly
+    }
+
+    /// A file without an `env` is assumed to be needed on both sides.
+    pub fn from_env(env: Option<&Env>) -> Self {
+        match env {
+            Some(Env {
+                server: ProjectSupportRange::Unsupported,
+                ..
+            }) => Placement::ClientOnly,
+            Some(Env {
+                client: ProjectSupportRange::Unsupported,
+                ..
+            }) => Placement::ServerOnly,
+            _ => Placement::Both,
+        }
+    }
+}
+
+impl From<Side> for Placement {
+    fn from(value: Side) -> Self {
+        match value {
+            Side::Both => Placement::Both,
+            Side::Client => Placement::ClientOnly,
+            Side::Server => Placement::ServerOnly,
+        }
+    }
+}
+
+/// Jars that could not be read, with the error for each.
+pub type JarErrors = Vec<(PathBuf, jar::Error)>;
+
+/// The side information of a mod jar.
+#[derive(Serialize, PartialEq, Eq, Debug, Clone)]
+pub struct ModSides {
+    pub path: PathBuf,
+    /// Every mod id provided by the jar.
+    pub ids: Vec<String>,
+    pub placement: Placement,
+    /// Mod ids the jar requires when running on a dedicated server.
+    pub server_dependencies: Vec<String>,
+}
+
+impl ModSides {
+    pub fn from_fabric(path: PathBuf, manifest: &FabricManifest) -> Self {
+        let ids = std::iter::once(&manifest.id)
+            .chain(&manifest.provides)
+            .map(ToString::to_string)
+            .collect();
+
+        Self {
+            path,
+            ids,
+            placement: Side::from(manifest.environment).into(),
+            server_dependencies: manifest.depends.keys().map(ToString::to_string).collect(),
+        }
+    }
+
+    /// A forge jar is client only when it sets `clientSideOnly` or every mod in it ignores
+    /// the server version, as set by `displayTest="IGNORE_ALL_VERSION"`.
+    pub fn from_forge(path: PathBuf, manifest: &UnsubstitutedForgeManifest) -> Self {
+        let display_test = |value: DisplayTest| {
+            !manifest.mods.is_empty() && manifest.mods.iter().all(|m| m.display_test == Some(value))
+        };
+
+        let placement = if manifest.client_side_only || display_test(DisplayTest::IgnoreAllVersion)
+        {
+            Placement::ClientOnly
+        } else if display_test(DisplayTest::IgnoreServerVersion) {
+            Placement::ServerOnly
+        } else {
+            Placement::Both
+        };
+
+        let server_dependencies = manifest
+            .dependencies
+            .values()
+            .flatten()
+            .filter(|dep| dep.is_mandatory() && dep.side.applies_to(Side::Server))
+            .map(|dep| dep.mod_id.clone())
+            .collect();
+
+        Self {
+            path,
+            ids: manifest.mods.iter().map(|m| m.mod_id.0.clone()).collect(),
+            placement,
+            server_dependencies,
+        }
+    }
+
+    /// Read the side information from a jar, Fabric metadata is preferred over Forge
+    /// and NeoForge metadata, returns `None` if the jar has none of them.
+    pub fn from_jar(path: impl AsRef<Path>) -> jar::Result<Option<Self>> {
+        let path = path.as_ref();
+        let mut detected = Jar::open(path)?.detect();
+
+        for manifest in &detected.loaders {
+            match manifest {
+                LoaderManifest::Fabric(manifest) => {
+                    return Ok(Some(Self::from_fabric(path.to_path_buf(), manifest)));
+                }
+                LoaderManifest::Forge(manifest) | LoaderManifest::NeoForge(manifest) => {
+                    return Ok(Some(Self::from_forge(path.to_path_buf(), manifest)));
+                }
+                _ => {}
+            }
+        }
+
+        if detected.errors.is_empty() {
+            Ok(None)
+        } else {
+            Err(detected.errors.remove(0))
+        }
+    }
+
+    /// Read the side information of every `.jar` in a directory.
+    /// Jars without any mod metadata are skipped, jars that cannot be read are returned
+    /// with their error instead of stopping the scan.
+    pub fn read_dir(dir: impl AsRef<Path>) -> jar::Result<(Vec<Self>, JarErrors)> {
+        let mut paths = vec![];
+        for entry in fs::read_dir(dir)? {
+            let path = entry?.path();
+            if path.extension().is_some_and(|ext| ext == "jar") {
+                paths.push(path);
+            }
+        }
+        paths.sort();
+
+        let mut mods = vec![];
+        let mut errors = vec![];
+        for path in paths {
+            match Self::from_jar(&path) {
+                Ok(Some(value)) => mods.push(value),
+                Ok(None) => {}
+                Err(e) => errors.push((path, e)),
+            }
+        }
+        Ok((mods, errors))
+    }
+}
+
+/// The mods of a dedicated server, see [`ServerPack::new`].
+#[derive(Serialize, PartialEq, Eq, Debug, Default)]
+pub struct ServerPack {
+    pub server: Vec<PathBuf>,
+    pub client_only: Vec<PathBuf>,
+    pub client_only_dependencies: Vec<ClientOnlyDependency>,
+}
+
+/// A client only mod that is required by a mod that is installed on the server.
+#[derive(Serialize, PartialEq, Eq, Debug, Clone)]
+pub struct ClientOnlyDependency {
+    pub path: PathBuf,
+    pub mod_id: String,
+    pub required_by: String,
+}
+
+impl ServerPack {
+    /// Split a list of mods into the ones that belong on a dedicated server and the client only ones.
+    pub fn new(mods: &[ModSides]) -> Self {
+        let client_only: HashMap<&str, &ModSides> = mods
+            .iter()
+            .filter(|m| !m.placement.on_server())
+            .flat_map(|m| m.ids.iter().map(move |id| (id.as_str(), m)))
+            .collect();
+
+        let mut pack = Self::default();
+
+        for m in mods {
+            if !m.placement.on_server() {
+                pack.client_only.push(m.path.clone());
+                continue;
+            }
+
+            pack.server.push(m.path.clone());
+
+            for dep in &m.server_dependencies {
+                if let Some(dependency) = client_only.get(dep.as_str()) {
+                    pack.client_only_dependencies.push(ClientOnlyDependency {
+                        path: dependency.path.clone(),
+                        mod_id: dep.clone(),
+                        required_by: m.ids.first().cloned().unwrap_or_default(),
+                    });
+                }
+            }
+        }
+
+        pack
+    }
+}
+
+/// The files of a Modrinth modpack that should be installed on a dedicated server.
+pub fn server_files(manifest: &MrManifest) -> impl Iterator<Item = &modrinth::File> {
+    manifest
+        .files
+        .iter()
+        .filter(|file| Placement::from_env(file.env.as_ref()).on_server())
+}
+
+/// Create a server side Modrinth modpack by removing the client only files.
+pub fn server_manifest(manifest: &MrManifest) -> MrManifest {
+    MrManifest {
+        files: server_files(manifest).cloned().collect(),
+        ..manifest.clone()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::io::Write;
+
+    use zip::{ZipWriter, write::SimpleFileOptions};
+
+    use super::*;
+
+    #[test]
+    fn server_pack_from_manifests() {
+        let sodium: FabricManifest = serde_json::from_str(
+            r#"{ "schemaVersion": 1, "id": "sodium", "version": "1.0.0", "environment": "client" }"#,
+        )
+        .unwrap();
+        let lithium: FabricManifest = serde_json::from_str(
+            r#"{ "schemaVersion": 1, "id": "lithium", "version": "1.0.0", "environment": "*" }"#,
+        )
+        .unwrap();
+        let badserver: FabricManifest = serde_json::from_str(
+            r#"{
+                "schemaVersion": 1,
+                "id": "badserver",
+                "version": "1.0.0",
+                "environment": "server",
+                "depends": { "sodium": "*" }
+            }"#,
+        )
+        .unwrap();
+        let forge: UnsubstitutedForgeManifest = toml::from_str(
+            r#"
+            modLoader="javafml"
+            loaderVersion="[41,)"
+            license="MIT"
+
+            [[mods]]
+            modId="zoomify"
+            displayTest="IGNORE_ALL_VERSION"
+            "#,
+        )
+        .unwrap();
+
+        let mods = [
+            ModSides::from_fabric("sodium.jar".into(), &sodium),
+            ModSides::from_fabric("lithium.jar".into(), &lithium),
+            ModSides::from_fabric("badserver.jar".into(), &badserver),
+            ModSides::from_forge("zoomify.jar".into(), &forge),
+        ];
+
+        assert_eq!(
+            ServerPack::new(&mods),
+            ServerPack {
+                server: vec!["lithium.jar".into(), "badserver.jar".into()],
+                client_only: vec!["sodium.jar".into(), "zoomify.jar".into()],
+                client_only_dependencies: vec![ClientOnlyDependency {
+                    path: "sodium.jar".into(),
+                    mod_id: "sodium".into(),
+                    required_by: "badserver".into(),
+                }],
+            }
+        );
+    }
+
+    #[test]
+    fn server_manifest_filters_client_files() {
+        let s = r#"{
+            "formatVersion": 1,
+            "game": "minecraft",
+            "versionId": "1",
+            "name": "Pack",
+            "files": [
+                {
+                    "path": "mods/sodium.jar",
+                    "hashes": {},
+                    "env": { "client": "required", "server": "unsupported" },
+                    "downloads": [],
+                    "fileSize": 0
+                },
+                {
+                    "path": "mods/lithium.jar",
+                    "hashes": {},
+                    "env": { "client": "optional", "server": "optional" },
+                    "downloads": [],
+                    "fileSize": 0
+                },
+                {
+                    "path": "mods/fabric-api.jar",
+                    "hashes": {},
+                    "downloads": [],
+                    "fileSize": 0
+                }
+            ],
+            "dependencies": {}
+        }"#;
+        let manifest: MrManifest = serde_json::from_str(s).unwrap();
+
+        let server = server_manifest(&manifest);
+        let paths: Vec<_> = server.files.iter().map(|f| f.path.clone()).collect();
+        assert_eq!(
+            paths,
+            vec![
+                PathBuf::from("mods/lithium.jar"),
+                "mods/fabric-api.jar".into()
+            ]
+        );
+        assert_eq!(server.name, manifest.name);
+    }
+
+    #[test]
+    fn read_dir_skips_unreadable_jars() {
+        let dir = tempfile::tempdir().unwrap();
+        let write_jar = |name: &str, files: &[(&str, &str)]| {
+            let mut writer = ZipWriter::new(fs::File::create(dir.path().join(name)).unwrap());
+            for (name, contents) in files {
+                writer
+                    .start_file(*name, SimpleFileOptions::default())
+                    .unwrap();
+                writer.write_all(contents.as_bytes()).unwrap();
+            }
+            writer.finish().unwrap();
+        };
+
+        write_jar(
+            "a-neoforge.jar",
+            &[(
+                jar::NEOFORGE_MANIFEST,
+                r#"
+                modLoader="javafml"
+                loaderVersion="[1,)"
+                license="MIT"
+
+                [[mods]]
+                modId="zoomify"
+                displayTest="IGNORE_ALL_VERSION"
+                "#,
+            )],
+        );
+        write_jar(
+            "b-broken.jar",
+            &[(jar::FABRIC_MANIFEST, r#"{ "schemaVersion": 1 }"#)],
+        );
+        fs::write(dir.path().join("c-not-a-zip.jar"), "not a zip").unwrap();
+        write_jar(
+            "d-fabric.jar",
+            &[(
+                jar::FABRIC_MANIFEST,
+                r#"{ "schemaVersion": 1, "id": "lithium", "version": "1.0.0" }"#,
+            )],
+        );
+        write_jar("e-library.jar", &[("com/example/Lib.class", "")]);
+
+        let (mods, errors) = ModSides::read_dir(dir.path()).unwrap();
+        let ids: Vec<_> = mods.iter().map(|m| m.ids[0].as_str()).collect();
+        assert_eq!(ids, ["zoomify", "lithium"]);
+        assert_eq!(mods[0].placement, Placement::ClientOnly);
+
+        let failed: Vec<_> = errors
+            .iter()
+            .map(|(path, _)| path.file_name().unwrap())
+            .collect();
+        assert_eq!(failed, ["b-broken.jar", "c-not-a-zip.jar"]);
+        assert!(matches!(errors[0].1, jar::Error::Json(_)));
+    }
+}
@@ -0,0 +1,24 @@
+use serde::{Deserialize, Serialize};
+
+/// A `quilt.mod.json` file.
+/// Only the identifying fields of the `quilt_loader` section are read.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug)]
+pub struct QuiltManifest {
+    pub schema_version: u64,
+    pub quilt_loader: QuiltLoader,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug)]
+pub struct QuiltLoader {
+    pub group: String,
+    pub id: String,
+    pub version: String,
+    #[serde(default)]
+    pub metadata: QuiltMetadata,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Default)]
+pub struct QuiltMetadata {
+    pub name: Option<String>,
+    pub description: Option<String>,
+}